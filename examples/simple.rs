@@ -1,6 +1,8 @@
 use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::ErrorKind;
 use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
-use uf_dps3xx::{Config, DPS3xx, Register};
+use std::io::{self, Write};
+use uf_dps3xx::{Config, DPS3xx, Error, Register};
 
 const ADDR: u8 = 0x77;
 
@@ -10,7 +12,7 @@ impl DelayNs for NoopDelay {
     fn delay_ns(&mut self, _ns: u32) {}
 }
 
-fn main() {
+fn main() -> Result<(), Error<ErrorKind>> {
     let expectations = [
         I2cTransaction::write_read(ADDR, vec![Register::PROD_ID.addr()], vec![0x10]),
         I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
@@ -31,7 +33,14 @@ fn main() {
         I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
         I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
         I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0x80]),
-        I2cTransaction::write_read(ADDR, vec![Register::COEFF_REG_1.addr()], vec![0; 18]),
+        // C0 = 40 (20 degrees Celsius), C00 = 100000 (Pa), all other coefficients 0
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            vec![
+                0x02, 0x80, 0x00, 0x18, 0x6A, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ],
+        ),
         I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
         I2cTransaction::write_read(ADDR, vec![Register::PSR_B2.addr()], vec![0x00, 0x04, 0x00]), // 1024
         I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
@@ -40,10 +49,11 @@ fn main() {
 
     let mut i2c = I2cMock::new(&expectations);
     let config = Config::new();
-    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config)?;
     let mut delay = NoopDelay;
-    let mut dps = dps.init_and_calibrate(&mut delay).unwrap();
-    let pres = dps.read_pressure_calibrated().unwrap();
+    let mut dps = dps.init_and_calibrate(&mut delay)?;
+    let pres = dps.read_pressure_calibrated()?;
     i2c.done();
-    println!("Done: {pres}")
+    writeln!(io::stdout(), "Done: {pres}").ok();
+    Ok(())
 }
@@ -0,0 +1,152 @@
+//! Barometric altitude helpers (ISA model), `no_std` without `libm`.
+//!
+//! All pressures are in Pa, altitudes in m and temperatures in degrees Celsius.
+
+/// ISA mean sea level pressure, used as reference for pressure altitude (flight levels)
+pub const STANDARD_PRESSURE_PA: f32 = 101_325.0;
+
+/// ISA sea level temperature in K
+const ISA_T0_K: f32 = 288.15;
+/// ISA troposphere temperature lapse rate in K/m
+const ISA_LAPSE_K_PER_M: f32 = 0.0065;
+/// R * L / (g * M) for the ISA troposphere
+const ISA_EXPONENT: f32 = 0.190_263;
+/// Specific gas constant of dry air in J/(kg K)
+const R_DRY_AIR: f32 = 287.053;
+/// Standard gravity in m/s^2
+const G0: f32 = 9.806_65;
+const CELSIUS_TO_KELVIN: f32 = 273.15;
+
+const LN_2: f32 = core::f32::consts::LN_2;
+
+/// Altitude above the level where the pressure equals `qnh_pa`.
+///
+/// With the local QNH this is the altitude above mean sea level.
+pub fn altitude(pressure_pa: f32, qnh_pa: f32) -> f32 {
+    ISA_T0_K / ISA_LAPSE_K_PER_M * (1.0 - powf(pressure_pa / qnh_pa, ISA_EXPONENT))
+}
+
+/// Standard pressure altitude, i.e. [`altitude`] referenced to [`STANDARD_PRESSURE_PA`].
+pub fn pressure_altitude(pressure_pa: f32) -> f32 {
+    altitude(pressure_pa, STANDARD_PRESSURE_PA)
+}
+
+/// Height above an airfield whose station pressure is `qfe_pa`.
+pub fn height_above_qfe(pressure_pa: f32, qfe_pa: f32) -> f32 {
+    altitude(pressure_pa, qfe_pa)
+}
+
+/// Sea level pressure (QNH) for a pressure measured at a known altitude.
+///
+/// Inverse of [`altitude`].
+pub fn sea_level_pressure(pressure_pa: f32, altitude_m: f32) -> f32 {
+    let ratio = 1.0 - altitude_m * ISA_LAPSE_K_PER_M / ISA_T0_K;
+    pressure_pa / powf(ratio, 1.0 / ISA_EXPONENT)
+}
+
+/// Altitude above the `reference_pa` level using the hypsometric equation with a measured
+/// temperature instead of the ISA lapse rate.
+///
+/// Assumes an isothermal layer at `temperature_c`, so this is more accurate than [`altitude`]
+/// for small height differences on days far from ISA conditions.
+pub fn altitude_temperature_corrected(
+    pressure_pa: f32,
+    reference_pa: f32,
+    temperature_c: f32,
+) -> f32 {
+    let temperature_k = temperature_c + CELSIUS_TO_KELVIN;
+    R_DRY_AIR * temperature_k / G0 * ln(reference_pa / pressure_pa)
+}
+
+/// `base^exponent` for positive `base`
+fn powf(base: f32, exponent: f32) -> f32 {
+    exp(exponent * ln(base))
+}
+
+/// Natural logarithm for positive, normal `x`
+fn ln(x: f32) -> f32 {
+    // x = m * 2^e with m in [sqrt(0.5), sqrt(2))
+    let bits = x.to_bits();
+    let mut e = ((bits >> 23) & 0xFF) as i32 - 127;
+    let mut m = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000);
+    if m > core::f32::consts::SQRT_2 {
+        m *= 0.5;
+        e += 1;
+    }
+    // ln(m) = 2 * atanh(s), s = (m - 1) / (m + 1), |s| < 0.172
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let series = 1.0 + s2 * (1.0 / 3.0 + s2 * (1.0 / 5.0 + s2 * (1.0 / 7.0 + s2 / 9.0)));
+    2.0 * s * series + e as f32 * LN_2
+}
+
+/// Exponential for `x` within the normal `f32` range
+fn exp(x: f32) -> f32 {
+    // x = k * ln2 + r with |r| <= ln2 / 2
+    let k = (x / LN_2 + if x < 0.0 { -0.5 } else { 0.5 }) as i32;
+    let r = x - k as f32 * LN_2;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..=8 {
+        term *= r / n as f32;
+        sum += term;
+    }
+    sum * f32::from_bits(((k + 127) as u32) << 23)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn test_ln_exp() {
+        for x in [1.0e-3_f32, 0.5, 0.9, 1.0, 1.2, 2.0, 10.0, 101_325.0] {
+            assert_close(ln(x), x.ln(), 1.0e-6 * x.ln().abs().max(1.0));
+        }
+        for x in [-10.0_f32, -1.0, -0.1, 0.0, 0.3, 1.0, 5.0, 20.0] {
+            assert_close(exp(x), x.exp(), 1.0e-6 * x.exp());
+        }
+    }
+
+    #[test]
+    fn test_pressure_altitude_isa_table() {
+        assert_close(pressure_altitude(STANDARD_PRESSURE_PA), 0.0, 0.01);
+        assert_close(pressure_altitude(89_874.6), 1_000.0, 0.1);
+        assert_close(pressure_altitude(54_019.9), 5_000.0, 0.5);
+        assert_close(pressure_altitude(107_477.7), -500.0, 0.1);
+    }
+
+    #[test]
+    fn test_sea_level_pressure_inverts_altitude() {
+        let qnh = 102_100.0;
+        for pressure in [80_000.0, 95_000.0, 101_000.0] {
+            let h = altitude(pressure, qnh);
+            assert_close(sea_level_pressure(pressure, h), qnh, 0.5);
+        }
+    }
+
+    #[test]
+    fn test_height_above_qfe() {
+        assert_close(height_above_qfe(97_000.0, 97_000.0), 0.0, 0.01);
+        // ~8.3 m per hPa close to sea level
+        assert_close(height_above_qfe(100_900.0, 101_000.0), 8.3, 0.1);
+    }
+
+    #[test]
+    fn test_temperature_corrected_altitude() {
+        // At ISA mean layer temperature both models agree
+        let h = altitude(89_874.6, STANDARD_PRESSURE_PA);
+        let corrected = altitude_temperature_corrected(89_874.6, STANDARD_PRESSURE_PA, 11.75);
+        assert_close(corrected, h, 1.0);
+        // Warmer air column is less dense, so same pressure drop means more height
+        let warm = altitude_temperature_corrected(89_874.6, STANDARD_PRESSURE_PA, 30.0);
+        assert!(warm > corrected, "warm {warm} <= {corrected}");
+    }
+}
@@ -0,0 +1,346 @@
+//! Typed contents of the configuration and status registers, see section 7 of the datasheet.
+//!
+//! Read and write them with [`crate::DPS3xx::read_register`],
+//! [`crate::DPS3xx::write_register`] and [`crate::DPS3xx::modify_register`].
+
+use crate::config::{PressureRate, PressureResolution, TemperatureRate, TemperatureResolution};
+use crate::device::{MeasurementMode, Status};
+use crate::fifo::FifoStatus;
+use crate::interrupt::InterruptStatus;
+use crate::register::Register;
+
+/// Register with a typed representation
+pub trait RegisterBits: Copy {
+    const REGISTER: Register;
+
+    fn from_bits(bits: u8) -> Self;
+
+    fn into_bits(self) -> u8;
+}
+
+/// Register that can be read
+pub trait ReadableRegister: RegisterBits {}
+
+/// Register that can be written
+pub trait WritableRegister: RegisterBits {}
+
+macro_rules! register_bits {
+    ($ty:ty, $reg:ident $(, $access:ident)+) => {
+        impl RegisterBits for $ty {
+            const REGISTER: Register = Register::$reg;
+
+            fn from_bits(bits: u8) -> Self {
+                <$ty>::from_bits(bits)
+            }
+
+            fn into_bits(self) -> u8 {
+                <$ty>::into_bits(self)
+            }
+        }
+
+        $(impl $access for $ty {})+
+    };
+}
+
+const fn bit(bits: u8, n: u8) -> bool {
+    bits & (1 << n) != 0
+}
+
+/// PRS_CFG: pressure measurement rate and oversampling
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrsCfg {
+    /// PM_RATE, bits 6:4
+    pub rate: PressureRate,
+    /// PM_PRC, bits 3:0. Values above 7 are invalid and decoded from bits 2:0.
+    pub oversampling: PressureResolution,
+    /// Reserved bit 7, kept on read/modify/write
+    pub reserved: bool,
+}
+
+impl PrsCfg {
+    pub const fn from_bits(bits: u8) -> Self {
+        Self {
+            rate: PressureRate::from_bits(bits >> 4),
+            oversampling: PressureResolution::from_bits(bits),
+            reserved: bit(bits, 7),
+        }
+    }
+
+    pub const fn into_bits(self) -> u8 {
+        ((self.reserved as u8) << 7) | ((self.rate as u8) << 4) | self.oversampling as u8
+    }
+}
+
+/// TEMP_CFG: temperature sensor, measurement rate and oversampling
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TempCfg {
+    /// TMP_EXT, bit 7: external (MEMS element) instead of internal (ASIC) sensor
+    pub external: bool,
+    /// TMP_RATE, bits 6:4
+    pub rate: TemperatureRate,
+    /// TMP_PRC, bits 2:0
+    pub oversampling: TemperatureResolution,
+    /// Reserved bit 3, kept on read/modify/write
+    pub reserved: bool,
+}
+
+impl TempCfg {
+    pub const fn from_bits(bits: u8) -> Self {
+        Self {
+            external: bit(bits, 7),
+            rate: TemperatureRate::from_bits(bits >> 4),
+            oversampling: TemperatureResolution::from_bits(bits),
+            reserved: bit(bits, 3),
+        }
+    }
+
+    pub const fn into_bits(self) -> u8 {
+        ((self.external as u8) << 7)
+            | ((self.rate as u8) << 4)
+            | ((self.reserved as u8) << 3)
+            | self.oversampling as u8
+    }
+}
+
+/// MEAS_CFG: ready flags and measurement mode.
+///
+/// The ready flags are read-only, only `mode` takes effect on write.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MeasCfg {
+    /// COEF_RDY, bit 7
+    pub coef_ready: bool,
+    /// SENSOR_RDY, bit 6
+    pub init_complete: bool,
+    /// TMP_RDY, bit 5
+    pub temp_ready: bool,
+    /// PRS_RDY, bit 4
+    pub pres_ready: bool,
+    /// Reserved bit 3, kept on read/modify/write
+    pub reserved: bool,
+    /// MEAS_CTRL, bits 2:0. `None` is idle (standby), reserved values also decode to `None`.
+    pub mode: Option<MeasurementMode>,
+}
+
+impl MeasCfg {
+    pub const fn from_bits(bits: u8) -> Self {
+        Self {
+            coef_ready: bit(bits, 7),
+            init_complete: bit(bits, 6),
+            temp_ready: bit(bits, 5),
+            pres_ready: bit(bits, 4),
+            reserved: bit(bits, 3),
+            mode: MeasurementMode::from_meas_ctrl(bits),
+        }
+    }
+
+    pub const fn into_bits(self) -> u8 {
+        let meas_ctrl = match self.mode {
+            Some(mode) => mode.meas_ctrl(),
+            None => 0,
+        };
+        ((self.coef_ready as u8) << 7)
+            | ((self.init_complete as u8) << 6)
+            | ((self.temp_ready as u8) << 5)
+            | ((self.pres_ready as u8) << 4)
+            | ((self.reserved as u8) << 3)
+            | meas_ctrl
+    }
+
+    /// Ready flags only
+    pub const fn status(&self) -> Status {
+        Status {
+            coef_ready: self.coef_ready,
+            init_complete: self.init_complete,
+            temp_ready: self.temp_ready,
+            pres_ready: self.pres_ready,
+        }
+    }
+}
+
+/// CFG_REG: interrupt, result shift, FIFO and SPI configuration
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CfgReg {
+    /// INT_HL, bit 7: interrupt active high
+    pub int_hl: bool,
+    /// INT_FIFO, bit 6
+    pub int_fifo: bool,
+    /// INT_TMP, bit 5
+    pub int_temp: bool,
+    /// INT_PRS, bit 4
+    pub int_pres: bool,
+    /// T_SHIFT, bit 3
+    pub temp_shift: bool,
+    /// P_SHIFT, bit 2
+    pub pres_shift: bool,
+    /// FIFO_EN, bit 1
+    pub fifo_enable: bool,
+    /// SPI_MODE, bit 0: 3-wire instead of 4-wire SPI
+    pub spi_mode: bool,
+}
+
+impl CfgReg {
+    pub const fn from_bits(bits: u8) -> Self {
+        Self {
+            int_hl: bit(bits, 7),
+            int_fifo: bit(bits, 6),
+            int_temp: bit(bits, 5),
+            int_pres: bit(bits, 4),
+            temp_shift: bit(bits, 3),
+            pres_shift: bit(bits, 2),
+            fifo_enable: bit(bits, 1),
+            spi_mode: bit(bits, 0),
+        }
+    }
+
+    pub const fn into_bits(self) -> u8 {
+        ((self.int_hl as u8) << 7)
+            | ((self.int_fifo as u8) << 6)
+            | ((self.int_temp as u8) << 5)
+            | ((self.int_pres as u8) << 4)
+            | ((self.temp_shift as u8) << 3)
+            | ((self.pres_shift as u8) << 2)
+            | ((self.fifo_enable as u8) << 1)
+            | self.spi_mode as u8
+    }
+}
+
+/// RESET: FIFO flush and soft reset commands
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResetReg {
+    /// FIFO_FLUSH, bit 7
+    pub fifo_flush: bool,
+    /// SOFT_RST, bits 3:0 set to 0b1001
+    pub soft_reset: bool,
+}
+
+impl ResetReg {
+    const SOFT_RST: u8 = 0b1001;
+
+    /// Empty the FIFO
+    pub const FLUSH_FIFO: Self = Self {
+        fifo_flush: true,
+        soft_reset: false,
+    };
+
+    /// Full reset, also flushing the FIFO
+    pub const SOFT_RESET: Self = Self {
+        fifo_flush: true,
+        soft_reset: true,
+    };
+
+    pub const fn from_bits(bits: u8) -> Self {
+        Self {
+            fifo_flush: bit(bits, 7),
+            soft_reset: bits & 0x0F == Self::SOFT_RST,
+        }
+    }
+
+    pub const fn into_bits(self) -> u8 {
+        let soft_rst = if self.soft_reset { Self::SOFT_RST } else { 0 };
+        ((self.fifo_flush as u8) << 7) | soft_rst
+    }
+}
+
+/// TMP_COEF_SRCE: temperature sensor the calibration coefficients are based on
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TmpCoefSrce {
+    /// TMP_COEF_SRCE, bit 7: external (MEMS element) instead of internal (ASIC) sensor
+    pub external: bool,
+}
+
+impl TmpCoefSrce {
+    pub const fn from_bits(bits: u8) -> Self {
+        Self {
+            external: bit(bits, 7),
+        }
+    }
+
+    pub const fn into_bits(self) -> u8 {
+        (self.external as u8) << 7
+    }
+}
+
+register_bits!(PrsCfg, PRS_CFG, ReadableRegister, WritableRegister);
+register_bits!(TempCfg, TEMP_CFG, ReadableRegister, WritableRegister);
+register_bits!(MeasCfg, MEAS_CFG, ReadableRegister, WritableRegister);
+register_bits!(CfgReg, CFG_REG, ReadableRegister, WritableRegister);
+register_bits!(InterruptStatus, INT_STS, ReadableRegister);
+register_bits!(FifoStatus, FIFO_STS, ReadableRegister);
+register_bits!(ResetReg, RESET, WritableRegister);
+register_bits!(TmpCoefSrce, TMP_COEF_SRCE, ReadableRegister);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_all_values() {
+        for bits in 0..=u8::MAX {
+            let pm_prc_valid = bits & 0x08 == 0;
+            if pm_prc_valid {
+                assert_eq!(
+                    PrsCfg::from_bits(bits).into_bits(),
+                    bits,
+                    "PRS_CFG {bits:#04x}"
+                );
+            }
+            assert_eq!(
+                TempCfg::from_bits(bits).into_bits(),
+                bits,
+                "TEMP_CFG {bits:#04x}"
+            );
+            assert_eq!(
+                CfgReg::from_bits(bits).into_bits(),
+                bits,
+                "CFG_REG {bits:#04x}"
+            );
+            let meas_ctrl_valid = !matches!(bits & 0x07, 0b011 | 0b100);
+            if meas_ctrl_valid {
+                assert_eq!(
+                    MeasCfg::from_bits(bits).into_bits(),
+                    bits,
+                    "MEAS_CFG {bits:#04x}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_fields() {
+        let prs_cfg = PrsCfg::from_bits(0x26);
+        assert_eq!(prs_cfg.rate, PressureRate::_4_SPS);
+        assert_eq!(prs_cfg.oversampling, PressureResolution::_64_SAMPLES);
+        assert!(!prs_cfg.reserved);
+
+        let temp_cfg = TempCfg::from_bits(0xA0);
+        assert!(temp_cfg.external);
+        assert_eq!(temp_cfg.rate, TemperatureRate::_4_SPS);
+
+        let meas_cfg = MeasCfg::from_bits(0xC7);
+        assert!(meas_cfg.coef_ready && meas_cfg.init_complete);
+        assert_eq!(
+            meas_cfg.mode,
+            Some(MeasurementMode::BackgroundPressureAndTemperature)
+        );
+        assert_eq!(MeasCfg::from_bits(0x03).mode, None);
+
+        assert_eq!(ResetReg::SOFT_RESET.into_bits(), 0x89);
+        assert_eq!(ResetReg::FLUSH_FIFO.into_bits(), 0x80);
+        assert_eq!(ResetReg::from_bits(0x89), ResetReg::SOFT_RESET);
+        assert!(TmpCoefSrce::from_bits(0x80).external);
+        assert_eq!(InterruptStatus::from_bits(0x05).into_bits(), 0x05);
+        assert_eq!(FifoStatus::from_bits(0x02).into_bits(), 0x02);
+    }
+}
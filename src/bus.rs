@@ -1,16 +1,34 @@
 use crate::register::Register;
+#[cfg(feature = "async")]
+use core::future::Future;
 use embedded_hal::i2c::I2c;
+use embedded_hal::spi::{Operation, SpiDevice};
 
-pub(crate) trait Bus {
+mod sealed {
+    /// Keeps [`super::Bus`] and [`super::AsyncBus`] implemented only by this crate's transports
+    pub trait Sealed {}
+}
+
+pub(crate) use sealed::Sealed;
+
+/// Register access used by the driver, implemented by every supported transport.
+///
+/// Public so generic code can name `DPS3xx<B, S>` with a `B: Bus` bound, sealed so new
+/// transports can be added without a breaking change.
+pub trait Bus: Sealed {
     type Error;
 
+    /// True when the transport is SPI, where 3-wire mode must be selected before the first read.
+    const SPI: bool;
+
     fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), Self::Error>;
     fn write_addr(&mut self, addr: u8, value: u8) -> Result<(), Self::Error>;
     fn read_reg(&mut self, reg: Register) -> Result<u8, Self::Error>;
     fn read_many(&mut self, start: Register, buf: &mut [u8]) -> Result<(), Self::Error>;
 }
 
-pub(crate) struct I2cBus<I2C> {
+/// I2C transport.
+pub struct I2cBus<I2C> {
     i2c: I2C,
     address: u8,
 }
@@ -25,12 +43,16 @@ impl<I2C> I2cBus<I2C> {
     }
 }
 
+impl<I2C> Sealed for I2cBus<I2C> {}
+
 impl<I2C, I2CError> Bus for I2cBus<I2C>
 where
     I2C: I2c<Error = I2CError>,
 {
     type Error = I2CError;
 
+    const SPI: bool = false;
+
     fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), Self::Error> {
         let bytes = [reg.addr(), value];
         self.i2c.write(self.address, &bytes)
@@ -52,3 +74,109 @@ where
         self.i2c.write_read(self.address, &[start.addr()], buf)
     }
 }
+
+/// Set in the address byte of an SPI transaction to read, cleared to write.
+const SPI_READ: u8 = 0x80;
+
+/// SPI transport, in either 4-wire or 3-wire mode.
+///
+/// Chip select is handled by the [`SpiDevice`] implementation.
+pub struct SpiBus<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> SpiBus<SPI> {
+    pub(crate) fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    pub(crate) fn release(self) -> SPI {
+        self.spi
+    }
+}
+
+impl<SPI> Sealed for SpiBus<SPI> {}
+
+impl<SPI, SPIError> Bus for SpiBus<SPI>
+where
+    SPI: SpiDevice<Error = SPIError>,
+{
+    type Error = SPIError;
+
+    const SPI: bool = true;
+
+    fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), Self::Error> {
+        self.write_addr(reg.addr(), value)
+    }
+
+    fn write_addr(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {
+        let bytes = [addr & !SPI_READ, value];
+        self.spi.write(&bytes)
+    }
+
+    fn read_reg(&mut self, reg: Register) -> Result<u8, Self::Error> {
+        let mut buffer: [u8; 1] = [0];
+        self.read_many(reg, &mut buffer)?;
+        Ok(buffer[0])
+    }
+
+    fn read_many(&mut self, start: Register, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.spi.transaction(&mut [
+            Operation::Write(&[start.addr() | SPI_READ]),
+            Operation::Read(buf),
+        ])
+    }
+}
+
+/// Async counterpart of [`Bus`], used by [`crate::DPS3xxAsync`].
+///
+/// Spelled with `impl Future` instead of `async fn`, which would warn for a public trait.
+#[cfg(feature = "async")]
+pub trait AsyncBus: Sealed {
+    type Error;
+
+    fn write_reg(
+        &mut self,
+        reg: Register,
+        value: u8,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+    fn write_addr(&mut self, addr: u8, value: u8) -> impl Future<Output = Result<(), Self::Error>>;
+    fn read_reg(&mut self, reg: Register) -> impl Future<Output = Result<u8, Self::Error>>;
+    fn read_many(
+        &mut self,
+        start: Register,
+        buf: &mut [u8],
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+#[cfg(feature = "async")]
+impl<I2C, I2CError> AsyncBus for I2cBus<I2C>
+where
+    I2C: embedded_hal_async::i2c::I2c<Error = I2CError>,
+{
+    type Error = I2CError;
+
+    async fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), Self::Error> {
+        let bytes = [reg.addr(), value];
+        self.i2c.write(self.address, &bytes).await
+    }
+
+    async fn write_addr(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {
+        let bytes = [addr, value];
+        self.i2c.write(self.address, &bytes).await
+    }
+
+    async fn read_reg(&mut self, reg: Register) -> Result<u8, Self::Error> {
+        let mut buffer: [u8; 1] = [0];
+        self.i2c
+            .write_read(self.address, &[reg.addr()], &mut buffer)
+            .await?;
+        Ok(buffer[0])
+    }
+
+    async fn read_many(&mut self, start: Register, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c
+            .write_read(self.address, &[start.addr()], buf)
+            .await
+    }
+}
@@ -1,15 +1,207 @@
-#[derive(Default, Debug, Clone, Copy)]
+use core::ops::RangeInclusive;
+
+/// Size of the on-chip coefficient block COEF (0x10..0x21)
+pub const COEFFS_LEN: usize = 18;
+
+/// Format version stored in the first byte of a calibration blob
+const BLOB_VERSION: u8 = 1;
+
+/// Size of [`CalibrationCoeffs::to_blob`]: version, coefficient block and CRC-16
+pub const CALIBRATION_BLOB_LEN: usize = 1 + COEFFS_LEN + 2;
+
+/// Range of a two's complement field of `bits` width
+const fn field_range(bits: u32) -> RangeInclusive<i32> {
+    -(1 << (bits - 1))..=(1 << (bits - 1)) - 1
+}
+
+/// Coefficient field widths of COEF_REG_1..18, see Sec 8.11. No per-part value ranges are
+/// published, so these only catch values that cannot come from the sensor.
+const C0_RANGE: RangeInclusive<i32> = field_range(12);
+const C1_RANGE: RangeInclusive<i32> = field_range(12);
+const C00_RANGE: RangeInclusive<i32> = field_range(20);
+const C10_RANGE: RangeInclusive<i32> = field_range(20);
+const C01_RANGE: RangeInclusive<i32> = field_range(16);
+const C11_RANGE: RangeInclusive<i32> = field_range(16);
+const C20_RANGE: RangeInclusive<i32> = field_range(16);
+const C21_RANGE: RangeInclusive<i32> = field_range(16);
+const C30_RANGE: RangeInclusive<i32> = field_range(16);
+
+/// Factory calibration coefficients, see Sec 8.11
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(non_snake_case)]
-pub(crate) struct CalibrationCoeffs {
-    pub C0: i32,
-    pub C1: i32,
-    pub C00: i32,
-    pub C01: i32,
-    pub C10: i32,
-    pub C11: i32,
-    pub C20: i32,
-    pub C21: i32,
-    pub C30: i32,
+pub struct CalibrationCoeffs {
+    pub(crate) C0: i32,
+    pub(crate) C1: i32,
+    pub(crate) C00: i32,
+    pub(crate) C01: i32,
+    pub(crate) C10: i32,
+    pub(crate) C11: i32,
+    pub(crate) C20: i32,
+    pub(crate) C21: i32,
+    pub(crate) C30: i32,
+}
+
+/// Why [`CalibrationCoeffs::from_blob`] rejected a blob
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalibrationBlobError {
+    UnsupportedVersion(u8),
+    ChecksumMismatch,
+}
+
+impl CalibrationCoeffs {
+    pub fn c0(&self) -> i32 {
+        self.C0
+    }
+
+    pub fn c1(&self) -> i32 {
+        self.C1
+    }
+
+    pub fn c00(&self) -> i32 {
+        self.C00
+    }
+
+    pub fn c10(&self) -> i32 {
+        self.C10
+    }
+
+    pub fn c01(&self) -> i32 {
+        self.C01
+    }
+
+    pub fn c11(&self) -> i32 {
+        self.C11
+    }
+
+    pub fn c20(&self) -> i32 {
+        self.C20
+    }
+
+    pub fn c21(&self) -> i32 {
+        self.C21
+    }
+
+    pub fn c30(&self) -> i32 {
+        self.C30
+    }
+
+    /// Parse the on-chip layout of COEF_REG_1..18
+    pub fn decode(bytes: &[u8; COEFFS_LEN]) -> Self {
+        let mut coeffs = Self::default();
+        process_calibration_coefficients(&mut coeffs, &mut bytes.clone());
+        coeffs
+    }
+
+    /// Whether any term is set and every term fits its on-chip field width
+    pub fn is_plausible(&self) -> bool {
+        *self != Self::default()
+            && C0_RANGE.contains(&self.C0)
+            && C1_RANGE.contains(&self.C1)
+            && C00_RANGE.contains(&self.C00)
+            && C10_RANGE.contains(&self.C10)
+            && C01_RANGE.contains(&self.C01)
+            && C11_RANGE.contains(&self.C11)
+            && C20_RANGE.contains(&self.C20)
+            && C21_RANGE.contains(&self.C21)
+            && C30_RANGE.contains(&self.C30)
+    }
+
+    /// Decode a coefficient block read from the sensor, `None` if it looks corrupted.
+    ///
+    /// Rejects the all-0x00 and all-0xFF patterns of a failed bus read and all-zero terms.
+    /// Bit errors within a field cannot be detected here, use [`crate::Config::coeff_double_read`].
+    pub(crate) fn checked_decode(bytes: &[u8; COEFFS_LEN]) -> Option<Self> {
+        if bytes.iter().all(|b| *b == 0x00) || bytes.iter().all(|b| *b == 0xFF) {
+            return None;
+        }
+        let coeffs = Self::decode(bytes);
+        coeffs.is_plausible().then_some(coeffs)
+    }
+
+    /// Pack into the on-chip layout of COEF_REG_1..18, inverse of [`Self::decode`]
+    pub fn encode(&self) -> [u8; COEFFS_LEN] {
+        let c0 = self.C0 as u32 & 0xFFF;
+        let c1 = self.C1 as u32 & 0xFFF;
+        let c00 = self.C00 as u32 & 0xF_FFFF;
+        let c10 = self.C10 as u32 & 0xF_FFFF;
+        let [c01_h, c01_l] = (self.C01 as u16).to_be_bytes();
+        let [c11_h, c11_l] = (self.C11 as u16).to_be_bytes();
+        let [c20_h, c20_l] = (self.C20 as u16).to_be_bytes();
+        let [c21_h, c21_l] = (self.C21 as u16).to_be_bytes();
+        let [c30_h, c30_l] = (self.C30 as u16).to_be_bytes();
+        [
+            (c0 >> 4) as u8,
+            (((c0 & 0x0F) << 4) | (c1 >> 8)) as u8,
+            c1 as u8,
+            (c00 >> 12) as u8,
+            (c00 >> 4) as u8,
+            (((c00 & 0x0F) << 4) | (c10 >> 16)) as u8,
+            (c10 >> 8) as u8,
+            c10 as u8,
+            c01_h,
+            c01_l,
+            c11_h,
+            c11_l,
+            c20_h,
+            c20_l,
+            c21_h,
+            c21_l,
+            c30_h,
+            c30_l,
+        ]
+    }
+
+    /// Versioned blob with a CRC-16/CCITT checksum, for storing the coefficients in flash
+    pub fn to_blob(&self) -> [u8; CALIBRATION_BLOB_LEN] {
+        let mut blob = [0; CALIBRATION_BLOB_LEN];
+        let (version, rest) = blob.split_at_mut(1);
+        let (coeffs, crc) = rest.split_at_mut(COEFFS_LEN);
+        version.copy_from_slice(&[BLOB_VERSION]);
+        coeffs.copy_from_slice(&self.encode());
+        let checksum = crc16(&[BLOB_VERSION]);
+        let checksum = crc16_update(checksum, coeffs);
+        crc.copy_from_slice(&checksum.to_be_bytes());
+        blob
+    }
+
+    /// Restore coefficients written by [`Self::to_blob`]
+    pub fn from_blob(blob: &[u8; CALIBRATION_BLOB_LEN]) -> Result<Self, CalibrationBlobError> {
+        let (payload, crc) = blob.split_at(1 + COEFFS_LEN);
+        let (version, coeffs) = payload.split_at(1);
+        if version != [BLOB_VERSION] {
+            return Err(CalibrationBlobError::UnsupportedVersion(
+                version.first().copied().unwrap_or_default(),
+            ));
+        }
+        if crc16(payload).to_be_bytes() != crc {
+            return Err(CalibrationBlobError::ChecksumMismatch);
+        }
+        let mut bytes = [0; COEFFS_LEN];
+        bytes.copy_from_slice(coeffs);
+        Ok(Self::decode(&bytes))
+    }
+}
+
+/// CRC-16/CCITT-FALSE
+fn crc16(data: &[u8]) -> u16 {
+    crc16_update(0xFFFF, data)
+}
+
+fn crc16_update(mut crc: u16, data: &[u8]) -> u16 {
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
 }
 
 pub(crate) fn process_calibration_coefficients(
@@ -57,6 +249,70 @@ pub(crate) fn calibrate_pressure(
         + (temp_scaled * pres_scaled * (coeffs.C11 as f32 + pres_scaled * coeffs.C21 as f32))
 }
 
+/// See section 4.9.2 in the datasheet.
+pub(crate) fn calibrate_temperature(coeffs: &CalibrationCoeffs, temp_scaled: f32) -> f32 {
+    (coeffs.C0 as f32 * 0.5) + (coeffs.C1 as f32 * temp_scaled)
+}
+
+/// Fraction bits of the scaled raw values used by the fixed-point path
+pub(crate) const FIXED_FRAC_BITS: u32 = 30;
+
+/// Scaled raw values are clamped to +-2.0 in the fixed-point path so the i64 arithmetic can not
+/// overflow. Real parts stay well below 1.0 over the whole operating range.
+const FIXED_SCALED_LIMIT: i64 = 2 << FIXED_FRAC_BITS;
+
+/// Pressure returned by the fixed-point path is in Pa multiplied by this value
+pub const PRESSURE_FIXED_SCALE: i32 = 100;
+
+/// Temperature returned by the fixed-point path is in degrees Celsius multiplied by this value
+pub const TEMPERATURE_FIXED_SCALE: i32 = 100;
+
+/// Integer-only version of [`calibrate_temperature`], returns centi-degrees Celsius.
+///
+/// Result is within 0.01 degrees of the `f32` path (rounding to the output resolution).
+/// `temp_scaled` has [`FIXED_FRAC_BITS`] fraction bits.
+pub(crate) fn calibrate_temperature_fixed(coeffs: &CalibrationCoeffs, temp_scaled: i64) -> i32 {
+    let y = temp_scaled.clamp(-FIXED_SCALED_LIMIT, FIXED_SCALED_LIMIT);
+    let scale = TEMPERATURE_FIXED_SCALE as i64;
+    let c0_half = (coeffs.C0 as i64 * scale) / 2;
+    let c1_term = (coeffs.C1 as i64 * scale * y + (1 << (FIXED_FRAC_BITS - 1))) >> FIXED_FRAC_BITS;
+    (c0_half + c1_term) as i32
+}
+
+/// Integer-only version of [`calibrate_pressure`], returns Pa * [`PRESSURE_FIXED_SCALE`].
+///
+/// Over the full coefficient range and scaled inputs within +-1.0 the result is within 0.25 Pa
+/// of the `f32` path, most of that being `f32` rounding (within 0.02 Pa of an `f64` reference).
+/// `pres_scaled` and `temp_scaled` have [`FIXED_FRAC_BITS`] fraction bits. First order terms are
+/// computed with 30 fraction bits, the second and third order ones with 24 (14 for the inner
+/// Horner step) to stay inside i64.
+pub(crate) fn calibrate_pressure_fixed(
+    coeffs: &CalibrationCoeffs,
+    pres_scaled: i64,
+    temp_scaled: i64,
+) -> i32 {
+    let x = pres_scaled.clamp(-FIXED_SCALED_LIMIT, FIXED_SCALED_LIMIT);
+    let y = temp_scaled.clamp(-FIXED_SCALED_LIMIT, FIXED_SCALED_LIMIT);
+
+    // Q30
+    let linear =
+        ((coeffs.C00 as i64) << FIXED_FRAC_BITS) + coeffs.C10 as i64 * x + coeffs.C01 as i64 * y;
+
+    // Q24
+    let xs = x >> 6;
+    let ys = y >> 6;
+    let x2 = (xs * xs) >> 24;
+    let xy = (xs * ys) >> 24;
+    // Q14
+    let c20_c30 = ((coeffs.C20 as i64) << 14) + ((coeffs.C30 as i64 * xs) >> 10);
+    let c11_c21 = ((coeffs.C11 as i64) << 14) + ((coeffs.C21 as i64 * xs) >> 10);
+    // Q24 * Q14 = Q38 -> Q30
+    let nonlinear = (x2 * c20_c30 + xy * c11_c21) >> 8;
+
+    let total = linear + nonlinear;
+    ((total * PRESSURE_FIXED_SCALE as i64 + (1 << (FIXED_FRAC_BITS - 1))) >> FIXED_FRAC_BITS) as i32
+}
+
 pub(crate) fn get_twos_complement(val: u32, length: u8) -> i32 {
     let mut ret = val as i32;
     if (val & ((1) << (length - 1))) > 0 {
@@ -64,3 +320,170 @@ pub(crate) fn get_twos_complement(val: u32, length: u8) -> i32 {
     }
     ret
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device_internal::{scale_raw, scale_raw_fixed, SCALE_FACTORS_INT};
+
+    /// xorshift64, enough to sweep the coefficient space deterministically
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Uniform signed value of `bits` width, the range of a two's complement coefficient
+        fn signed(&mut self, bits: u8) -> i32 {
+            get_twos_complement((self.next() & ((1 << bits) - 1)) as u32, bits)
+        }
+
+        /// Raw result for oversampling `osr` with a scaled value within +-1.0
+        fn raw(&mut self, osr: usize) -> i32 {
+            let k = SCALE_FACTORS_INT[osr] as i64;
+            ((self.next() % (2 * k as u64 + 1)) as i64 - k) as i32
+        }
+    }
+
+    /// Coefficients read from a DPS310
+    const REAL_COEFFS: [u8; COEFFS_LEN] = [
+        0x0C, 0xDE, 0xFC, 0x13, 0x9B, 0x1F, 0x34, 0x8D, 0xF3, 0xF4, 0x05, 0x1A, 0xE1, 0xD6, 0x00,
+        0x35, 0xFC, 0x6C,
+    ];
+
+    #[test]
+    fn test_checked_decode() {
+        let coeffs = CalibrationCoeffs::checked_decode(&REAL_COEFFS);
+        assert!(coeffs.is_some(), "real coefficients should pass");
+
+        assert_eq!(CalibrationCoeffs::checked_decode(&[0x00; COEFFS_LEN]), None);
+        assert_eq!(CalibrationCoeffs::checked_decode(&[0xFF; COEFFS_LEN]), None);
+    }
+
+    #[test]
+    fn test_is_plausible_field_widths() {
+        let mut coeffs = CalibrationCoeffs::decode(&REAL_COEFFS);
+        assert!(coeffs.is_plausible());
+
+        coeffs.C0 = 2047;
+        coeffs.C00 = -524_288;
+        coeffs.C30 = 32_767;
+        assert!(coeffs.is_plausible());
+
+        coeffs.C0 = 2048;
+        assert!(!coeffs.is_plausible());
+
+        assert!(!CalibrationCoeffs::default().is_plausible());
+    }
+
+    #[test]
+    fn test_crc16_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let mut rng = Rng(0x1234_5678_9ABC_DEF0);
+        for _ in 0..1000 {
+            let coeffs = random_coeffs(&mut rng);
+            assert_eq!(CalibrationCoeffs::decode(&coeffs.encode()), coeffs);
+
+            let mut bytes = [0; COEFFS_LEN];
+            for byte in bytes.iter_mut() {
+                *byte = rng.next() as u8;
+            }
+            assert_eq!(CalibrationCoeffs::decode(&bytes).encode(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_blob_round_trip_and_corruption() {
+        let mut rng = Rng(0x0F0F_0F0F_0F0F_0F0F);
+        let coeffs = random_coeffs(&mut rng);
+        let blob = coeffs.to_blob();
+        assert_eq!(CalibrationCoeffs::from_blob(&blob), Ok(coeffs));
+
+        let mut corrupted = blob;
+        corrupted[5] ^= 0x01;
+        assert_eq!(
+            CalibrationCoeffs::from_blob(&corrupted),
+            Err(CalibrationBlobError::ChecksumMismatch)
+        );
+
+        let mut future = blob;
+        future[0] = 2;
+        assert_eq!(
+            CalibrationCoeffs::from_blob(&future),
+            Err(CalibrationBlobError::UnsupportedVersion(2))
+        );
+    }
+
+    fn random_coeffs(rng: &mut Rng) -> CalibrationCoeffs {
+        CalibrationCoeffs {
+            C0: rng.signed(12),
+            C1: rng.signed(12),
+            C00: rng.signed(20),
+            C10: rng.signed(20),
+            C01: rng.signed(16),
+            C11: rng.signed(16),
+            C20: rng.signed(16),
+            C21: rng.signed(16),
+            C30: rng.signed(16),
+        }
+    }
+
+    /// Largest difference from the `f32` path, see [`calibrate_pressure_fixed`]
+    const PRESSURE_FIXED_MAX_ERROR_PA: f32 = 0.25;
+    /// Largest difference from the `f32` path, see [`calibrate_temperature_fixed`]
+    const TEMPERATURE_FIXED_MAX_ERROR_C: f32 = 0.01;
+
+    #[test]
+    fn test_fixed_point_matches_f32() {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+        for _ in 0..100_000 {
+            let coeffs = random_coeffs(&mut rng);
+            let osr = (rng.next() % 8) as u8;
+            let pres_raw = rng.raw(osr as usize);
+            let temp_raw = rng.raw(osr as usize);
+
+            let temp_scaled = scale_raw(temp_raw, osr).unwrap();
+            let pres = calibrate_pressure(&coeffs, scale_raw(pres_raw, osr).unwrap(), temp_scaled);
+            let temp = calibrate_temperature(&coeffs, temp_scaled);
+
+            let temp_scaled_fixed = scale_raw_fixed(temp_raw, osr).unwrap();
+            let pres_fixed = calibrate_pressure_fixed(
+                &coeffs,
+                scale_raw_fixed(pres_raw, osr).unwrap(),
+                temp_scaled_fixed,
+            );
+            let temp_fixed = calibrate_temperature_fixed(&coeffs, temp_scaled_fixed);
+
+            let pres_error = (pres_fixed as f32 / PRESSURE_FIXED_SCALE as f32 - pres).abs();
+            let temp_error = (temp_fixed as f32 / TEMPERATURE_FIXED_SCALE as f32 - temp).abs();
+            assert!(
+                pres_error <= PRESSURE_FIXED_MAX_ERROR_PA,
+                "pressure off by {pres_error} Pa for {coeffs:?}, raw {pres_raw}/{temp_raw}"
+            );
+            assert!(
+                temp_error <= TEMPERATURE_FIXED_MAX_ERROR_C,
+                "temperature off by {temp_error} C for {coeffs:?}, raw {temp_raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixed_point_clamps_out_of_range_input() {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        let coeffs = random_coeffs(&mut rng);
+        let max = scale_raw_fixed(0x7F_FFFF, 4).unwrap();
+        let min = scale_raw_fixed(-0x80_0000, 4).unwrap();
+        // Must not overflow
+        let _ = calibrate_pressure_fixed(&coeffs, max, min);
+        let _ = calibrate_pressure_fixed(&coeffs, min, max);
+        let _ = calibrate_temperature_fixed(&coeffs, max);
+    }
+}
@@ -0,0 +1,78 @@
+/// REV_ID read back by the supported parts, DPS310 and DPS368 both report PROD_ID 0x10
+const REVISION_DPS3XX: u8 = 0x1;
+
+/// Sensor variant decoded from the PROD_ID register
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ChipVariant {
+    /// DPS310 or DPS368. Both report the same PROD_ID and share the register map, so the
+    /// two cannot be told apart through this register.
+    Dps3xx,
+    /// Product ID not supported by this driver
+    Unknown,
+}
+
+/// Decoded contents of the PROD_ID register
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ChipInfo {
+    /// PROD_ID bits 3:0
+    pub product: u8,
+    /// REV_ID, PROD_ID bits 7:4
+    pub revision: u8,
+    pub variant: ChipVariant,
+}
+
+impl ChipInfo {
+    /// Decode PROD_ID. As before this type existed, a part is supported when REV_ID is 1,
+    /// the product nibble is not checked.
+    pub fn from_id(id: u8) -> Self {
+        let revision = id >> 4;
+        let variant = if revision == REVISION_DPS3XX {
+            ChipVariant::Dps3xx
+        } else {
+            ChipVariant::Unknown
+        };
+        ChipInfo {
+            product: id & 0x0F,
+            revision,
+            variant,
+        }
+    }
+
+    /// Whether the undocumented temperature fuse-bit writes should be applied by default.
+    ///
+    /// True for every supported part: the affected revisions are not published, and the
+    /// vendor library applies the writes unconditionally as they do not harm unaffected
+    /// ICs. Can be overridden with [`crate::Config::temp_workaround`].
+    pub fn needs_temp_workaround(&self) -> bool {
+        self.variant == ChipVariant::Dps3xx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chip_info_from_id() {
+        let chip = ChipInfo::from_id(0x10);
+        assert_eq!(chip.product, 0);
+        assert_eq!(chip.revision, 1);
+        assert_eq!(chip.variant, ChipVariant::Dps3xx);
+        assert!(chip.needs_temp_workaround());
+
+        let chip = ChipInfo::from_id(0x1F);
+        assert_eq!(chip.product, 0xF);
+        assert_eq!(chip.revision, 1);
+        assert_eq!(chip.variant, ChipVariant::Dps3xx);
+        assert!(chip.needs_temp_workaround());
+
+        let chip = ChipInfo::from_id(0x20);
+        assert_eq!(chip.revision, 2);
+        assert_eq!(chip.variant, ChipVariant::Unknown);
+        assert!(!chip.needs_temp_workaround());
+    }
+}
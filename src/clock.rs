@@ -0,0 +1,126 @@
+use crate::config::Config;
+use crate::device_internal::{calc_busy_time_units, BUSYTIME_SCALING};
+use crate::fifo::FifoSample;
+
+/// Monotonic time source for timestamped reads
+pub trait Clock {
+    /// Current instant in µs, must never go backwards
+    fn now_us(&mut self) -> u64;
+}
+
+impl<F> Clock for F
+where
+    F: FnMut() -> u64,
+{
+    fn now_us(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// A value with the [`Clock`] instant it was measured at
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timestamped<T> {
+    /// Estimated start of the conversion in µs for background and FIFO results, the time of
+    /// the read otherwise
+    pub timestamp_us: u64,
+    pub value: T,
+}
+
+/// Duration of one conversion with oversampling bits `oversampling`, as in
+/// [`crate::calc_busy_time_ms`] but in µs
+pub(crate) fn conversion_time_us(oversampling: u8) -> u64 {
+    calc_busy_time_units(0, oversampling) as u64 * (1000 / BUSYTIME_SCALING) as u64
+}
+
+/// Background mode interval of rate bits `rate`, 1 to 128 per second
+fn period_us(rate: u8) -> u64 {
+    1_000_000 >> (rate & 0x07)
+}
+
+/// Timestamps for a FIFO burst read at `read_us`, oldest entry first.
+///
+/// The newest pressure and temperature finished just before the read, older ones are spaced by
+/// the configured rates.
+pub(crate) fn fifo_timestamps(
+    config: &Config,
+    read_us: u64,
+    samples: &[FifoSample],
+    out: &mut [Timestamped<FifoSample>],
+) {
+    let pres_res = config.pres_res.unwrap_or_default() as u8;
+    let temp_res = config.temp_res.unwrap_or_default() as u8;
+    let pres_period = period_us(config.pres_rate.unwrap_or_default() as u8);
+    let temp_period = period_us(config.temp_rate.unwrap_or_default() as u8);
+
+    let mut newer_pres = 0;
+    let mut newer_temp = 0;
+    for (sample, slot) in samples.iter().zip(out.iter_mut()).rev() {
+        let age = match sample {
+            FifoSample::Pressure(_) => {
+                newer_pres += 1;
+                conversion_time_us(pres_res) + (newer_pres - 1) * pres_period
+            }
+            FifoSample::Temperature(_) => {
+                newer_temp += 1;
+                conversion_time_us(temp_res) + (newer_temp - 1) * temp_period
+            }
+        };
+        *slot = Timestamped {
+            timestamp_us: read_us.saturating_sub(age),
+            value: *sample,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PressureRate, TemperatureRate};
+
+    #[test]
+    fn test_conversion_time() {
+        // Single sample: 3.6 ms
+        assert_eq!(conversion_time_us(0), 3_600);
+        // 64 times oversampling: 104.4 ms
+        assert_eq!(conversion_time_us(6), 104_400);
+    }
+
+    #[test]
+    fn test_fifo_timestamps_follow_rates() {
+        let mut config = Config::new();
+        config
+            .pres_rate(PressureRate::_4_SPS)
+            .temp_rate(TemperatureRate::_1_SPS);
+        let samples = [
+            FifoSample::Temperature(20.0),
+            FifoSample::Pressure(1.0),
+            FifoSample::Pressure(2.0),
+            FifoSample::Pressure(3.0),
+        ];
+        let mut out = [Timestamped {
+            timestamp_us: 0,
+            value: FifoSample::Pressure(0.0),
+        }; 4];
+
+        fifo_timestamps(&config, 1_000_000, &samples, &mut out);
+
+        let timestamps = out.map(|sample| sample.timestamp_us);
+        assert_eq!(timestamps, [996_400, 496_400, 746_400, 996_400]);
+        assert_eq!(out[0].value, FifoSample::Temperature(20.0));
+    }
+
+    #[test]
+    fn test_fifo_timestamps_saturate_at_zero() {
+        let samples = [FifoSample::Pressure(1.0), FifoSample::Pressure(2.0)];
+        let mut out = [Timestamped {
+            timestamp_us: 7,
+            value: FifoSample::Pressure(0.0),
+        }; 2];
+
+        fifo_timestamps(&Config::new(), 500_000, &samples, &mut out);
+
+        assert_eq!(out.map(|sample| sample.timestamp_us), [0, 496_400]);
+    }
+}
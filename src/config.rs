@@ -1,4 +1,7 @@
 #![allow(non_camel_case_types)]
+use crate::device::MeasurementMode;
+use crate::device_internal::max_busy_time_exceeded;
+
 /// Pressure rate
 #[repr(u8)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -20,6 +23,20 @@ impl PressureRate {
     pub const fn val(self) -> u8 {
         self as u8
     }
+
+    /// Decode the 3 bit register field, upper bits are ignored
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0x07 {
+            0b000 => Self::_1_SPS,
+            0b001 => Self::_2_SPS,
+            0b010 => Self::_4_SPS,
+            0b011 => Self::_8_SPS,
+            0b100 => Self::_16_SPS,
+            0b101 => Self::_32_SPS,
+            0b110 => Self::_64_SPS,
+            _ => Self::_128_SPS,
+        }
+    }
 }
 
 impl From<PressureRate> for u8 {
@@ -42,13 +59,27 @@ pub enum PressureResolution {
     _16_SAMPLES = 0b100,
     _32_SAMPLES = 0b101,
     _64_SAMPLES = 0b110,
-    _128_SAMPLES = 0b111, // Available for measurements in background mode only
+    _128_SAMPLES = 0b111, // Available for measurements in background mode only, see Config::validate
 }
 
 impl PressureResolution {
     pub const fn val(self) -> u8 {
         self as u8
     }
+
+    /// Decode the 3 bit register field, upper bits are ignored
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0x07 {
+            0b000 => Self::_1_SAMPLES,
+            0b001 => Self::_2_SAMPLES,
+            0b010 => Self::_4_SAMPLES,
+            0b011 => Self::_8_SAMPLES,
+            0b100 => Self::_16_SAMPLES,
+            0b101 => Self::_32_SAMPLES,
+            0b110 => Self::_64_SAMPLES,
+            _ => Self::_128_SAMPLES,
+        }
+    }
 }
 
 impl From<PressureResolution> for u8 {
@@ -78,6 +109,20 @@ impl TemperatureRate {
     pub const fn val(self) -> u8 {
         self as u8
     }
+
+    /// Decode the 3 bit register field, upper bits are ignored
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0x07 {
+            0b000 => Self::_1_SPS,
+            0b001 => Self::_2_SPS,
+            0b010 => Self::_4_SPS,
+            0b011 => Self::_8_SPS,
+            0b100 => Self::_16_SPS,
+            0b101 => Self::_32_SPS,
+            0b110 => Self::_64_SPS,
+            _ => Self::_128_SPS,
+        }
+    }
 }
 
 impl From<TemperatureRate> for u8 {
@@ -107,6 +152,20 @@ impl TemperatureResolution {
     pub const fn val(self) -> u8 {
         self as u8
     }
+
+    /// Decode the 3 bit register field, upper bits are ignored
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0x07 {
+            0b000 => Self::_1_SAMPLES,
+            0b001 => Self::_2_SAMPLES,
+            0b010 => Self::_4_SAMPLES,
+            0b011 => Self::_8_SAMPLES,
+            0b100 => Self::_16_SAMPLES,
+            0b101 => Self::_32_SAMPLES,
+            0b110 => Self::_64_SAMPLES,
+            _ => Self::_128_SAMPLES,
+        }
+    }
 }
 
 impl From<TemperatureResolution> for u8 {
@@ -117,6 +176,11 @@ impl From<TemperatureResolution> for u8 {
 
 /// Configuration struct
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::serde_support::UncheckedConfig")
+)]
 #[derive(Copy, Clone, Debug)]
 pub struct Config {
     pub(crate) pres_rate: Option<PressureRate>,
@@ -133,6 +197,9 @@ pub struct Config {
     pub(crate) fifo_enable: bool,
     pub(crate) spi_mode: bool,
     pub(crate) init_timeout_ms: u32,
+    pub(crate) temp_workaround: Option<bool>,
+    pub(crate) coeff_double_read: bool,
+    pub(crate) coeff_retries: u8,
 }
 
 impl Config {
@@ -153,9 +220,84 @@ impl Config {
             fifo_enable: false,
             spi_mode: false,
             init_timeout_ms: 5000,
+            temp_workaround: None,
+            coeff_double_read: false,
+            coeff_retries: 2,
         }
     }
 
+    /// Lowest power: 1 Hz pressure and temperature, no oversampling.
+    ///
+    /// About 2.5 Pa RMS precision and 3 µA, for
+    /// [`MeasurementMode::BackgroundPressureAndTemperature`] or one-shot readings.
+    pub fn low_power() -> Self {
+        let mut config = Self::new();
+        config
+            .pres_rate(PressureRate::_1_SPS)
+            .pres_res(PressureResolution::_1_SAMPLES)
+            .temp_rate(TemperatureRate::_1_SPS)
+            .temp_res(TemperatureResolution::_1_SAMPLES);
+        config
+    }
+
+    /// Weather station: 1 Hz pressure with 2 times oversampling, 1 Hz temperature.
+    ///
+    /// About 1 Pa RMS precision and 3 µA, for
+    /// [`MeasurementMode::BackgroundPressureAndTemperature`].
+    pub fn weather_station() -> Self {
+        let mut config = Self::new();
+        config
+            .pres_rate(PressureRate::_1_SPS)
+            .pres_res(PressureResolution::_2_SAMPLES)
+            .temp_rate(TemperatureRate::_1_SPS)
+            .temp_res(TemperatureResolution::_1_SAMPLES);
+        config
+    }
+
+    /// Indoor navigation (floor detection): 2 Hz pressure with 16 times oversampling,
+    /// 1 Hz temperature.
+    ///
+    /// About 0.35 Pa RMS precision (~3 cm) and 20 µA, for
+    /// [`MeasurementMode::BackgroundPressureAndTemperature`].
+    pub fn indoor_navigation() -> Self {
+        let mut config = Self::new();
+        config
+            .pres_rate(PressureRate::_2_SPS)
+            .pres_res(PressureResolution::_16_SAMPLES)
+            .temp_rate(TemperatureRate::_1_SPS)
+            .temp_res(TemperatureResolution::_1_SAMPLES);
+        config
+    }
+
+    /// Sports and fitness: 4 Hz pressure with 16 times oversampling, 4 Hz temperature.
+    ///
+    /// About 0.35 Pa RMS precision and 40 µA, for
+    /// [`MeasurementMode::BackgroundPressureAndTemperature`].
+    pub fn sports() -> Self {
+        let mut config = Self::new();
+        config
+            .pres_rate(PressureRate::_4_SPS)
+            .pres_res(PressureResolution::_16_SAMPLES)
+            .temp_rate(TemperatureRate::_4_SPS)
+            .temp_res(TemperatureResolution::_1_SAMPLES);
+        config
+    }
+
+    /// Drones and flight controllers: 64 Hz pressure with 4 times oversampling,
+    /// 16 Hz temperature.
+    ///
+    /// About 0.5 Pa RMS precision and 200 µA, for
+    /// [`MeasurementMode::BackgroundPressureAndTemperature`].
+    pub fn high_rate_flight() -> Self {
+        let mut config = Self::new();
+        config
+            .pres_rate(PressureRate::_64_SPS)
+            .pres_res(PressureResolution::_4_SAMPLES)
+            .temp_rate(TemperatureRate::_16_SPS)
+            .temp_res(TemperatureResolution::_1_SAMPLES);
+        config
+    }
+
     pub fn pres_rate(&mut self, rate: PressureRate) -> &mut Self {
         self.pres_rate = Some(rate);
         self
@@ -231,6 +373,134 @@ impl Config {
         self.init_timeout_ms = timeout_ms;
         self
     }
+
+    /// Force the temperature fuse-bit workaround on or off.
+    ///
+    /// By default it is applied to every supported part, see
+    /// [`crate::ChipInfo::needs_temp_workaround`].
+    pub fn temp_workaround(&mut self, apply: bool) -> &mut Self {
+        self.temp_workaround = Some(apply);
+        self
+    }
+
+    /// Read the coefficient block twice and require both reads to match
+    pub fn coeff_double_read(&mut self, enable: bool) -> &mut Self {
+        self.coeff_double_read = enable;
+        self
+    }
+
+    /// Re-reads of a corrupted coefficient block before [`crate::Error::InvalidCoefficients`]
+    /// is returned. Defaults to 2.
+    pub fn coeff_retries(&mut self, retries: u8) -> &mut Self {
+        self.coeff_retries = retries;
+        self
+    }
+}
+
+/// Every problem found by [`Config::validate`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConfigError {
+    /// Background measurement would need more than [`crate::MAX_BUSYTIME_UNITS`]
+    pub busy_time_exceeded: bool,
+    /// 128 times oversampling is only available in background mode
+    pub one_shot_oversampling: bool,
+    /// `temp_shift` set although temperature oversampling is 8 times or less
+    pub temp_shift_mismatch: bool,
+    /// `pres_shift` set although pressure oversampling is 8 times or less
+    pub pres_shift_mismatch: bool,
+    /// FIFO full interrupt enabled while the FIFO is disabled
+    pub fifo_interrupt_without_fifo: bool,
+    /// Interrupt enabled on 4-wire SPI, where SDO is the data output.
+    ///
+    /// Interrupts are output on SDO, which is free with I2C and 3-wire SPI only.
+    pub interrupt_on_4wire_spi: bool,
+}
+
+impl ConfigError {
+    fn any(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+impl core::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let violations = [
+            (self.busy_time_exceeded, "busy time exceeded"),
+            (
+                self.one_shot_oversampling,
+                "128x oversampling in one-shot mode",
+            ),
+            (
+                self.temp_shift_mismatch,
+                "temp_shift set at 8x oversampling or less",
+            ),
+            (
+                self.pres_shift_mismatch,
+                "pres_shift set at 8x oversampling or less",
+            ),
+            (
+                self.fifo_interrupt_without_fifo,
+                "FIFO interrupt with FIFO disabled",
+            ),
+            (self.interrupt_on_4wire_spi, "interrupt on 4-wire SPI"),
+        ];
+        f.write_str("invalid config:")?;
+        let mut separator = " ";
+        for (_, text) in violations.iter().filter(|(set, _)| *set) {
+            f.write_str(separator)?;
+            f.write_str(text)?;
+            separator = ", ";
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Check the config for use with `mode`.
+    ///
+    /// Runs without hardware. Interrupts on 4-wire SPI are only checked by
+    /// [`crate::DPS3xx::new_spi`], as the config alone does not know the bus.
+    pub fn validate(&self, mode: MeasurementMode) -> Result<(), ConfigError> {
+        self.check(Some(mode), false)
+    }
+
+    /// Mode independent checks, plus the ones for `mode` if given
+    pub(crate) fn check(
+        &self,
+        mode: Option<MeasurementMode>,
+        four_wire_spi: bool,
+    ) -> Result<(), ConfigError> {
+        let temp_res = self.temp_res.unwrap_or_default();
+        let pres_res = self.pres_res.unwrap_or_default();
+        let mut error = ConfigError {
+            temp_shift_mismatch: self.temp_shift
+                && temp_res as u8 <= TemperatureResolution::_8_SAMPLES as u8,
+            pres_shift_mismatch: self.pres_shift
+                && pres_res as u8 <= PressureResolution::_8_SAMPLES as u8,
+            fifo_interrupt_without_fifo: self.int_fifo && !self.fifo_enable,
+            interrupt_on_4wire_spi: four_wire_spi
+                && (self.int_fifo || self.int_temp || self.int_pres),
+            ..ConfigError::default()
+        };
+        if let Some(mode) = mode {
+            error.busy_time_exceeded = max_busy_time_exceeded(self, mode);
+            error.one_shot_oversampling = match mode {
+                MeasurementMode::OneShotPressure => pres_res == PressureResolution::_128_SAMPLES,
+                MeasurementMode::OneShotTemperature => {
+                    temp_res == TemperatureResolution::_128_SAMPLES
+                }
+                _ => false,
+            };
+        }
+
+        if error.any() {
+            Err(error)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl Default for Config {
@@ -261,6 +531,7 @@ mod tests {
         assert!(!cfg.fifo_enable);
         assert!(!cfg.spi_mode);
         assert_eq!(cfg.init_timeout_ms, 5000);
+        assert_eq!(cfg.temp_workaround, None);
     }
 
     #[test]
@@ -279,7 +550,8 @@ mod tests {
             .pres_shift(true)
             .fifo(false, true)
             .spi_mode(true)
-            .init_timeout_ms(10_000);
+            .init_timeout_ms(10_000)
+            .temp_workaround(false);
 
         assert_eq!(cfg.pres_rate, Some(PressureRate::_16_SPS));
         assert_eq!(cfg.pres_res, Some(PressureResolution::_8_SAMPLES));
@@ -295,5 +567,81 @@ mod tests {
         assert!(cfg.fifo_enable);
         assert!(cfg.spi_mode);
         assert_eq!(cfg.init_timeout_ms, 10_000);
+        assert_eq!(cfg.temp_workaround, Some(false));
+    }
+
+    #[test]
+    fn test_presets_pass_validation() {
+        for cfg in [
+            Config::low_power(),
+            Config::weather_station(),
+            Config::indoor_navigation(),
+            Config::sports(),
+            Config::high_rate_flight(),
+        ] {
+            assert_eq!(
+                cfg.validate(MeasurementMode::BackgroundPressureAndTemperature),
+                Ok(()),
+                "{cfg:?}"
+            );
+        }
+        assert_eq!(
+            Config::low_power().validate(MeasurementMode::OneShotPressure),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_default_config() {
+        let cfg = Config::new();
+        assert_eq!(cfg.validate(MeasurementMode::OneShotPressure), Ok(()));
+        assert_eq!(
+            cfg.validate(MeasurementMode::BackgroundPressureAndTemperature),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_lists_every_violation() {
+        let mut cfg = Config::new();
+        cfg.pres_rate(PressureRate::_128_SPS)
+            .pres_res(PressureResolution::_128_SAMPLES)
+            .temp_shift(true)
+            .int_fifo(true);
+
+        let error = cfg
+            .validate(MeasurementMode::BackgroundPressure)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            ConfigError {
+                busy_time_exceeded: true,
+                temp_shift_mismatch: true,
+                fifo_interrupt_without_fifo: true,
+                ..ConfigError::default()
+            }
+        );
+
+        let error = cfg.validate(MeasurementMode::OneShotPressure).unwrap_err();
+        assert!(error.one_shot_oversampling);
+        assert!(!error.busy_time_exceeded);
+    }
+
+    #[test]
+    fn test_validate_shift_and_spi() {
+        let mut cfg = Config::new();
+        cfg.pres_res(PressureResolution::_16_SAMPLES)
+            .pres_shift(true)
+            .int_pres(true);
+        assert_eq!(cfg.check(None, false), Ok(()));
+        assert_eq!(
+            cfg.check(None, true),
+            Err(ConfigError {
+                interrupt_on_4wire_spi: true,
+                ..ConfigError::default()
+            })
+        );
+        cfg.spi_mode(true);
+        assert_eq!(cfg.check(None, false), Ok(()));
     }
 }
@@ -0,0 +1,147 @@
+use crate::calibration::{
+    calibrate_pressure, calibrate_temperature, CalibrationCoeffs, COEFFS_LEN,
+};
+use crate::config::{PressureResolution, TemperatureResolution};
+use crate::device::Measurement;
+use crate::device_internal::{i24_from_bytes, pres_scale_factor, temp_scale_factor};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Size of a PSR_B2..TMP_B0 burst read
+pub const RAW_BURST_LEN: usize = 6;
+
+/// Converts raw register contents to compensated values without a sensor attached.
+///
+/// For decoding logged `PSR_B2..TMP_B0` bytes offline. The resolutions must match the
+/// PRS_CFG/TEMP_CFG settings the data was measured with.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RawDecoder {
+    coeffs: CalibrationCoeffs,
+    pres_factor: f32,
+    temp_factor: f32,
+}
+
+impl RawDecoder {
+    /// Decoder for the 18 byte coefficient block COEF_REG_1..18
+    pub fn new(
+        coeff_bytes: &[u8; COEFFS_LEN],
+        pres_res: PressureResolution,
+        temp_res: TemperatureResolution,
+    ) -> Self {
+        Self::from_coefficients(CalibrationCoeffs::decode(coeff_bytes), pres_res, temp_res)
+    }
+
+    pub fn from_coefficients(
+        coeffs: CalibrationCoeffs,
+        pres_res: PressureResolution,
+        temp_res: TemperatureResolution,
+    ) -> Self {
+        Self {
+            coeffs,
+            pres_factor: pres_scale_factor(pres_res),
+            temp_factor: temp_scale_factor(temp_res),
+        }
+    }
+
+    pub fn coefficients(&self) -> CalibrationCoeffs {
+        self.coeffs
+    }
+
+    /// Temperature in degrees Celsius from the TMP_B2..TMP_B0 bytes
+    pub fn temperature(&self, raw_temp: [u8; 3]) -> f32 {
+        calibrate_temperature(&self.coeffs, self.temp_scaled(raw_temp))
+    }
+
+    /// Pressure in Pa from the PSR_B2..PSR_B0 bytes, compensated with the temperature
+    /// measured alongside
+    pub fn pressure(&self, raw_pres: [u8; 3], raw_temp: [u8; 3]) -> f32 {
+        calibrate_pressure(
+            &self.coeffs,
+            self.pres_scaled(raw_pres),
+            self.temp_scaled(raw_temp),
+        )
+    }
+
+    /// Pressure and temperature from a PSR_B2..TMP_B0 burst, as in [`crate::DPS3xx::read_sample`]
+    pub fn measurement(&self, burst: &[u8; RAW_BURST_LEN]) -> Measurement {
+        let [p2, p1, p0, t2, t1, t0] = *burst;
+        Measurement {
+            pressure_pa: self.pressure([p2, p1, p0], [t2, t1, t0]),
+            temperature_c: self.temperature([t2, t1, t0]),
+        }
+    }
+
+    /// Decode a whole log of bursts
+    #[cfg(feature = "std")]
+    pub fn decode_records(&self, records: &[[u8; RAW_BURST_LEN]]) -> Vec<Measurement> {
+        records
+            .iter()
+            .map(|burst| self.measurement(burst))
+            .collect()
+    }
+
+    fn pres_scaled(&self, raw: [u8; 3]) -> f32 {
+        i24_from_bytes(raw) as f32 / self.pres_factor
+    }
+
+    fn temp_scaled(&self, raw: [u8; 3]) -> f32 {
+        i24_from_bytes(raw) as f32 / self.temp_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// C0 = 40 (20 degrees Celsius), C00 = 100000 (Pa), C10 = -40000
+    const COEFFS: [u8; COEFFS_LEN] = [
+        0x02, 0x80, 0x00, 0x18, 0x6A, 0x0F, 0x63, 0xC0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    #[test]
+    fn test_decode_burst() {
+        let decoder = RawDecoder::new(
+            &COEFFS,
+            PressureResolution::_1_SAMPLES,
+            TemperatureResolution::_1_SAMPLES,
+        );
+        // Raw pressure 262144 / 524288 = 0.5 scaled
+        let measurement = decoder.measurement(&[0x04, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(measurement.temperature_c, 20.0);
+        assert_eq!(measurement.pressure_pa, 100_000.0 - 20_000.0);
+        assert_eq!(
+            decoder.pressure([0x04, 0x00, 0x00], [0x00, 0x00, 0x00]),
+            measurement.pressure_pa
+        );
+    }
+
+    #[test]
+    fn test_decode_uses_resolution_scale_factor() {
+        let decoder = RawDecoder::new(
+            &COEFFS,
+            PressureResolution::_16_SAMPLES,
+            TemperatureResolution::_1_SAMPLES,
+        );
+        // Raw pressure -253952 / 253952 = -1.0 scaled
+        let pressure = decoder.pressure([0xFC, 0x20, 0x00], [0x00, 0x00, 0x00]);
+        assert_eq!(pressure, 100_000.0 + 40_000.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_decode_records() {
+        let decoder = RawDecoder::new(
+            &COEFFS,
+            PressureResolution::_1_SAMPLES,
+            TemperatureResolution::_1_SAMPLES,
+        );
+        let records = [[0; RAW_BURST_LEN], [0x04, 0x00, 0x00, 0x00, 0x00, 0x00]];
+        let pressures: Vec<f32> = decoder
+            .decode_records(&records)
+            .iter()
+            .map(|m| m.pressure_pa)
+            .collect();
+        assert_eq!(pressures, [100_000.0, 80_000.0]);
+    }
+}
@@ -1,15 +1,35 @@
-use crate::bus::{Bus, I2cBus};
+use crate::altitude::{altitude, altitude_temperature_corrected};
+use crate::bitfields::{
+    CfgReg, MeasCfg, ReadableRegister, ResetReg, TempCfg, TmpCoefSrce, WritableRegister,
+};
+use crate::bus::{Bus, I2cBus, SpiBus};
 use crate::calibration::{
-    calibrate_pressure, get_twos_complement, process_calibration_coefficients, CalibrationCoeffs,
+    calibrate_pressure, calibrate_pressure_fixed, calibrate_temperature,
+    calibrate_temperature_fixed, CalibrationCoeffs, COEFFS_LEN,
 };
-use crate::config::{Config, PressureResolution, TemperatureResolution};
+use crate::chip::ChipInfo;
+use crate::clock::{conversion_time_us, fifo_timestamps, Clock, Timestamped};
+use crate::config::{Config, ConfigError};
+use crate::decoder::{RawDecoder, RAW_BURST_LEN};
 use crate::device_internal::{
-    cfg_reg_value, prs_cfg_value, tmp_cfg_value, PRODUCT_ID, SCALE_FACTORS,
+    cfg_reg_value, i24_from_bytes, init_wait_ms, max_busy_time_exceeded, prs_cfg_value, scale_raw,
+    scale_raw_fixed, tmp_cfg_value, MEASUREMENT_POLL_MS, RESET_WAIT_MS, TEMP_WORKAROUND_WRITES,
+};
+use crate::dump::{RegisterDump, DUMP_BLOCK_LEN};
+use crate::fifo::{FifoEntry, FifoSample, FifoStatus, FIFO_SIZE};
+use crate::init::{
+    apply_temp_workaround, check_chip, check_coefficients, coefficient_retry, InitBudget,
+    InitState, InitStep, COEF_POLL_MS,
 };
+use crate::interrupt::{InterruptSample, InterruptStatus};
 use crate::register::Register;
+use crate::retry::{RetryBus, RetryConfig, RetryStats};
+use crate::self_test::{SelfTestReport, SELF_TEST_SAMPLES};
 use core::marker::PhantomData;
 use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, InputPin};
 use embedded_hal::i2c::I2c;
+use embedded_hal::spi::SpiDevice;
 
 pub use crate::device_internal::{
     calc_busy_time_ms, calc_busy_time_units, calc_total_wait_ms, BUSYTIME_FAILSAFE_MS,
@@ -30,6 +50,7 @@ pub struct Calibrated;
 pub struct InitInProgress;
 
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum InitPoll {
     Pending(u32),
@@ -37,6 +58,7 @@ pub enum InitPoll {
 }
 
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum InitStage {
     WaitingInitComplete,
@@ -51,16 +73,27 @@ impl IsConfigured for Calibrated {}
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug)]
 pub enum Error<I2CError> {
-    /// I2C Interface Error
+    /// Bus interface (I2C or SPI) error
     I2CError(I2CError),
     InvalidProductId,
     BusyTimeExceeded,
     CoefficientsNotReady,
     InitTimeout(InitStage),
     InvalidOversampling(u8),
+    /// Interrupt pin could not be read
+    PinError(#[cfg_attr(feature = "defmt", defmt(Debug2Format))] digital::ErrorKind),
+    /// Sensor in a [`crate::DPS3xxManager`] did not complete initialization
+    NotInitialized,
+    /// Rejected by [`Config::validate`] when creating the driver
+    InvalidConfig(ConfigError),
+    /// Coefficient block looks corrupted, see [`crate::CalibrationCoeffs::is_plausible`]
+    InvalidCoefficients,
+    /// One-shot result was not ready within twice [`calc_total_wait_ms`]
+    MeasurementTimeout,
 }
 
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum MeasurementMode {
     OneShotPressure,
@@ -71,7 +104,7 @@ pub enum MeasurementMode {
 }
 
 impl MeasurementMode {
-    const fn meas_ctrl(self) -> u8 {
+    pub(crate) const fn meas_ctrl(self) -> u8 {
         match self {
             Self::OneShotPressure => 0b001,
             Self::OneShotTemperature => 0b010,
@@ -80,9 +113,22 @@ impl MeasurementMode {
             Self::BackgroundPressureAndTemperature => 0b111,
         }
     }
+
+    /// Decode MEAS_CTRL (MEAS_CFG bits 2:0), `None` for idle or reserved values
+    pub(crate) const fn from_meas_ctrl(meas_ctrl: u8) -> Option<Self> {
+        match meas_ctrl & 0x07 {
+            0b001 => Some(Self::OneShotPressure),
+            0b010 => Some(Self::OneShotTemperature),
+            0b101 => Some(Self::BackgroundPressure),
+            0b110 => Some(Self::BackgroundTemperature),
+            0b111 => Some(Self::BackgroundPressureAndTemperature),
+            _ => None,
+        }
+    }
 }
 
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Status {
     pub coef_ready: bool,
@@ -92,163 +138,225 @@ pub struct Status {
 }
 
 impl Status {
-    fn from_bits(status: u8) -> Self {
-        Self {
-            coef_ready: (status & (1 << 7)) != 0,
-            init_complete: (status & (1 << 6)) != 0,
-            temp_ready: (status & (1 << 5)) != 0,
-            pres_ready: (status & (1 << 4)) != 0,
-        }
+    pub(crate) fn from_bits(status: u8) -> Self {
+        MeasCfg::from_bits(status).status()
     }
 }
 
+/// Pressure and temperature compensated from the same burst read
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    /// Pressure in Pa
+    pub pressure_pa: f32,
+    /// Temperature in degrees Celsius
+    pub temperature_c: f32,
+}
+
 impl<I2CError> From<I2CError> for Error<I2CError> {
     fn from(err: I2CError) -> Self {
         Error::I2CError(err)
     }
 }
 
-pub struct DPS3xx<I2C, S> {
-    bus: I2cBus<I2C>,
+/// Driver for one sensor on transport `B` ([`I2cBus`], [`SpiBus`] or a [`RetryBus`]) in
+/// typestate `S`.
+pub struct DPS3xx<B, S> {
+    bus: B,
     coeffs: CalibrationCoeffs,
     config: Config,
-    init_ready: bool,
-    init_temp_started: bool,
+    init: InitState,
     _state: PhantomData<S>,
 }
 
-impl<I2C, I2CError> DPS3xx<I2C, Unconfigured>
+/// [`DPS3xx`] on I2C, the type `DPS3xx<I2C, S>` named before SPI support
+pub type DPS3xxI2c<I2C, S> = DPS3xx<I2cBus<I2C>, S>;
+
+/// [`DPS3xx`] on SPI
+pub type DPS3xxSpi<SPI, S> = DPS3xx<SpiBus<SPI>, S>;
+
+impl<I2C, E> DPS3xx<I2cBus<I2C>, Unconfigured>
+where
+    I2C: I2c<Error = E>,
+{
+    pub fn new(i2c: I2C, address: u8, config: &Config) -> Result<Self, Error<E>> {
+        Self::with_bus(I2cBus::new(i2c, address), config)
+    }
+}
+
+impl<SPI, E> DPS3xx<SpiBus<SPI>, Unconfigured>
 where
-    I2C: I2c<Error = I2CError>,
+    SPI: SpiDevice<Error = E>,
 {
-    pub fn new(i2c: I2C, address: u8, config: &Config) -> Result<Self, Error<I2CError>> {
-        let dps3xx = Self {
-            bus: I2cBus::new(i2c, address),
+    /// Create a driver talking SPI. Chip select is driven by the [`SpiDevice`].
+    ///
+    /// Use [`Config::spi_mode`] to select 3-wire mode, it is applied by [`DPS3xx::start_init`]
+    /// before the first register read.
+    pub fn new_spi(spi: SPI, config: &Config) -> Result<Self, Error<E>> {
+        Self::with_bus(SpiBus::new(spi), config)
+    }
+}
+
+impl<B, E> DPS3xx<B, Unconfigured>
+where
+    B: Bus<Error = E>,
+{
+    /// Runs the mode independent checks of [`Config::validate`]
+    fn with_bus(bus: B, config: &Config) -> Result<Self, Error<E>> {
+        config
+            .check(None, B::SPI && !config.spi_mode)
+            .map_err(Error::InvalidConfig)?;
+        Ok(Self {
+            bus,
             coeffs: CalibrationCoeffs::default(),
             config: *config,
-            init_ready: false,
-            init_temp_started: false,
+            init: InitState::default(),
             _state: PhantomData,
-        };
-        Ok(dps3xx)
+        })
     }
 
-    pub fn start_init(mut self) -> Result<DPS3xx<I2C, InitInProgress>, Error<I2CError>> {
-        let id = self.get_product_id()?;
-        if (id & 0xF0) != (PRODUCT_ID & 0xF0) {
-            return Err(Error::InvalidProductId);
+    /// Wrap the bus to retry failed transactions as set in `retry`, waiting with `delay`.
+    ///
+    /// The delay is owned by the bus, [`DPS3xx::init_and_calibrate`] and friends still take
+    /// their own. Counters are read with [`DPS3xx::retry_stats`].
+    pub fn with_retry<D>(
+        self,
+        retry: &RetryConfig,
+        delay: D,
+    ) -> DPS3xx<RetryBus<B, D>, Unconfigured>
+    where
+        D: DelayNs,
+    {
+        DPS3xx {
+            bus: RetryBus::new(self.bus, delay, retry),
+            coeffs: self.coeffs,
+            config: self.config,
+            init: self.init,
+            _state: PhantomData,
+        }
+    }
+
+    pub fn start_init(mut self) -> Result<DPS3xx<B, InitInProgress>, Error<E>> {
+        self.begin_init()?;
+        Ok(self.into_state())
+    }
+
+    /// [`Self::start_init`] that hands the driver back on error, for [`crate::DPS3xxManager`]
+    pub(crate) fn try_start_init(mut self) -> Result<DPS3xx<B, InitInProgress>, (Self, Error<E>)> {
+        match self.begin_init() {
+            Ok(()) => Ok(self.into_state()),
+            Err(err) => Err((self, err)),
         }
+    }
+
+    fn begin_init(&mut self) -> Result<(), Error<E>> {
+        if B::SPI && self.config.spi_mode {
+            // Sensor wakes up in 4-wire mode, switch before reading anything back
+            self.write_register(CfgReg {
+                spi_mode: true,
+                ..CfgReg::default()
+            })?;
+        }
+
+        let chip = self.chip_info()?;
+        check_chip(chip)?;
         self.apply_config()?;
         self.standby()?;
 
-        self.apply_temp_workaround_registers()?;
-
-        self.init_ready = false;
-        self.init_temp_started = false;
+        if apply_temp_workaround(&self.config, chip) {
+            self.apply_temp_workaround_registers()?;
+        }
 
-        Ok(self.into_state())
+        self.init = InitState::default();
+        Ok(())
     }
 
-    pub fn init_and_calibrate<D>(
-        self,
-        delay: &mut D,
-    ) -> Result<DPS3xx<I2C, Calibrated>, Error<I2CError>>
+    /// Initialize and read the calibration coefficients, within [`Config::init_timeout_ms`].
+    ///
+    /// Consumes the driver, so nothing is left to inspect after [`Error::InitTimeout`]. To take
+    /// a [`DPS3xx::register_dump`] after a stall, call [`DPS3xx::start_init`] and
+    /// [`DPS3xx::poll_init`] with your own timeout instead, which keeps the [`InitInProgress`]
+    /// driver.
+    pub fn init_and_calibrate<D>(self, delay: &mut D) -> Result<DPS3xx<B, Calibrated>, Error<E>>
     where
         D: DelayNs,
     {
-        let mut dps = self.start_init()?;
-        let mut timeout_remaining_ms = dps.config.init_timeout_ms;
+        let dps = self.start_init()?;
+        let mut budget = InitBudget::new(&dps.config);
 
-        let mut dps = loop {
-            match dps.poll_init()? {
-                InitPoll::Pending(wait_ms) => {
-                    let stage = if dps.init_temp_started {
-                        InitStage::WaitingInitTempReady
-                    } else {
-                        InitStage::WaitingInitComplete
-                    };
-                    Self::delay_or_timeout(delay, &mut timeout_remaining_ms, wait_ms, stage)?;
-                }
-                InitPoll::Ready => match dps.finish_init() {
-                    Ok(dps) => break dps,
-                    Err(unfinished) => dps = unfinished,
-                },
-            }
-        };
+        let mut dps = dps.wait_init_complete(delay, &mut budget)?;
 
         while !dps.coef_ready()? {
-            Self::delay_or_timeout(
-                delay,
-                &mut timeout_remaining_ms,
-                10,
-                InitStage::WaitingCoefReady,
-            )?;
+            budget.take(COEF_POLL_MS, InitStage::WaitingCoefReady)?;
+            delay.delay_ms(COEF_POLL_MS);
         }
 
         dps.read_calibration_coefficients_unchecked()
     }
-
-    fn delay_or_timeout<D>(
-        delay: &mut D,
-        remaining_ms: &mut u32,
-        wait_ms: u32,
-        stage: InitStage,
-    ) -> Result<(), Error<I2CError>>
-    where
-        D: DelayNs,
-    {
-        if wait_ms > *remaining_ms {
-            return Err(Error::InitTimeout(stage));
-        }
-        delay.delay_ms(wait_ms);
-        *remaining_ms -= wait_ms;
-        Ok(())
-    }
 }
 
-impl<I2C, I2CError> DPS3xx<I2C, InitInProgress>
+impl<B, E> DPS3xx<B, InitInProgress>
 where
-    I2C: I2c<Error = I2CError>,
+    B: Bus<Error = E>,
 {
-    fn init_wait_ms(&self) -> u32 {
-        calc_total_wait_ms(
-            self.config.temp_rate.unwrap_or_default() as u8,
-            self.config.temp_res.unwrap_or_default() as u8,
-        )
-    }
-
-    pub fn poll_init(&mut self) -> Result<InitPoll, Error<I2CError>> {
-        if self.init_ready {
+    pub fn poll_init(&mut self) -> Result<InitPoll, Error<E>> {
+        if self.init.ready {
             return Ok(InitPoll::Ready);
         }
 
         let status = self.status()?;
-        if !status.init_complete {
-            return Ok(InitPoll::Pending(self.init_wait_ms()));
-        }
-
-        if !self.init_temp_started {
-            self.write_reg(
-                Register::MEAS_CFG,
-                MeasurementMode::OneShotTemperature.meas_ctrl(),
-            )?;
-            self.init_temp_started = true;
-            return Ok(InitPoll::Pending(self.init_wait_ms()));
+        match self.init.step(status) {
+            InitStep::Wait => {}
+            InitStep::StartTemperature => {
+                self.write_reg(
+                    Register::MEAS_CFG,
+                    MeasurementMode::OneShotTemperature.meas_ctrl(),
+                )?;
+                self.init.temp_started = true;
+            }
+            InitStep::FinishTemperature => {
+                let _ = self.read_i24(Register::TMP_B2)?;
+                self.standby()?;
+                self.init = InitState {
+                    ready: true,
+                    temp_started: false,
+                };
+                return Ok(InitPoll::Ready);
+            }
         }
+        Ok(InitPoll::Pending(init_wait_ms(&self.config)))
+    }
 
-        if !status.temp_ready {
-            return Ok(InitPoll::Pending(self.init_wait_ms()));
+    /// Poll until init is complete, sharing `budget` with the caller's other waits
+    fn wait_init_complete<D>(
+        mut self,
+        delay: &mut D,
+        budget: &mut InitBudget,
+    ) -> Result<DPS3xx<B, Configured>, Error<E>>
+    where
+        D: DelayNs,
+    {
+        loop {
+            match self.poll_init()? {
+                InitPoll::Pending(wait_ms) => {
+                    budget.take(wait_ms, self.init.stage())?;
+                    delay.delay_ms(wait_ms);
+                }
+                InitPoll::Ready => match self.finish_init() {
+                    Ok(dps) => return Ok(dps),
+                    Err(unfinished) => self = unfinished,
+                },
+            }
         }
+    }
 
-        let _ = self.read_i24(Register::TMP_B2)?;
-        self.standby()?;
-        self.init_ready = true;
-        self.init_temp_started = false;
-        Ok(InitPoll::Ready)
+    pub(crate) fn init_stage(&self) -> InitStage {
+        self.init.stage()
     }
 
-    pub fn finish_init(self) -> Result<DPS3xx<I2C, Configured>, Self> {
-        if self.init_ready {
+    pub fn finish_init(self) -> Result<DPS3xx<B, Configured>, Self> {
+        if self.init.ready {
             Ok(self.into_state())
         } else {
             Err(self)
@@ -256,18 +364,16 @@ where
     }
 }
 
-impl<I2C, I2CError> DPS3xx<I2C, Configured>
+impl<B, E> DPS3xx<B, Configured>
 where
-    I2C: I2c<Error = I2CError>,
+    B: Bus<Error = E>,
 {
     /// Read calibration coefficients. User must wait for `Self::coef_ready()` to return true before reading coefficients.
     ///
     /// Taken from official Arduino library, see <https://github.com/Infineon/DPS3xx-Pressure-Sensor/blob/888200c7efd8edb19ce69a2144e28ba31cdad449/src/Dps310.cpp#L89>
     ///
     /// See Sec 8.11
-    pub fn read_calibration_coefficients(
-        mut self,
-    ) -> Result<DPS3xx<I2C, Calibrated>, Error<I2CError>> {
+    pub fn read_calibration_coefficients(mut self) -> Result<DPS3xx<B, Calibrated>, Error<E>> {
         if !self.coef_ready()? {
             return Err(Error::CoefficientsNotReady);
         }
@@ -275,148 +381,221 @@ where
         self.read_calibration_coefficients_unchecked()
     }
 
-    fn read_calibration_coefficients_unchecked(
+    /// Read and check the coefficient block, re-reading up to `Config::coeff_retries` times
+    pub(crate) fn read_calibration_coefficients_unchecked(
         mut self,
-    ) -> Result<DPS3xx<I2C, Calibrated>, Error<I2CError>> {
-        let mut bytes: [u8; 18] = [0; 18];
-        self.bus.read_many(Register::COEFF_REG_1, &mut bytes)?;
-
-        process_calibration_coefficients(&mut self.coeffs, &mut bytes);
-
+    ) -> Result<DPS3xx<B, Calibrated>, Error<E>> {
+        self.coeffs = self.read_coefficients_retrying()?;
         Ok(self.into_state())
     }
-}
 
-impl<I2C, I2CError, S> DPS3xx<I2C, S>
-where
-    I2C: I2c<Error = I2CError>,
-{
-    fn max_busy_time_exceeded(&self, mode: MeasurementMode) -> bool {
-        let temp_rate = self.config.temp_rate.unwrap_or_default() as u8;
-        let temp_res = self.config.temp_res.unwrap_or_default() as u8;
-        let pres_rate = self.config.pres_rate.unwrap_or_default() as u8;
-        let pres_res = self.config.pres_res.unwrap_or_default() as u8;
-
-        match mode {
-            MeasurementMode::BackgroundPressure => {
-                calc_busy_time_units(pres_rate, pres_res) >= MAX_BUSYTIME_UNITS
-            }
-            MeasurementMode::BackgroundTemperature => {
-                calc_busy_time_units(temp_rate, temp_res) >= MAX_BUSYTIME_UNITS
+    /// [`Self::read_calibration_coefficients_unchecked`] that hands the driver back on error,
+    /// for [`crate::DPS3xxManager`]
+    pub(crate) fn try_read_calibration_coefficients(
+        mut self,
+    ) -> Result<DPS3xx<B, Calibrated>, (Self, Error<E>)> {
+        match self.read_coefficients_retrying() {
+            Ok(coeffs) => {
+                self.coeffs = coeffs;
+                Ok(self.into_state())
             }
-            MeasurementMode::BackgroundPressureAndTemperature => {
-                calc_busy_time_units(temp_rate, temp_res)
-                    + calc_busy_time_units(pres_rate, pres_res)
-                    >= MAX_BUSYTIME_UNITS
+            Err(err) => Err((self, err)),
+        }
+    }
+
+    fn read_coefficients_retrying(&mut self) -> Result<CalibrationCoeffs, Error<E>> {
+        let mut retries = self.config.coeff_retries;
+        loop {
+            match self.read_checked_coefficients() {
+                Err(err) if coefficient_retry(&mut retries, &err) => {}
+                result => return result,
             }
-            MeasurementMode::OneShotPressure | MeasurementMode::OneShotTemperature => false,
         }
     }
 
-    pub fn release(self) -> I2C {
-        self.bus.release()
+    fn read_checked_coefficients(&mut self) -> Result<CalibrationCoeffs, Error<E>> {
+        let mut bytes = [0; COEFFS_LEN];
+        self.bus.read_many(Register::COEFF_REG_1, &mut bytes)?;
+        if !self.config.coeff_double_read {
+            return check_coefficients(&bytes, None);
+        }
+        let mut again = [0; COEFFS_LEN];
+        self.bus.read_many(Register::COEFF_REG_1, &mut again)?;
+        check_coefficients(&bytes, Some(&again))
     }
 
-    pub fn status(&mut self) -> Result<Status, Error<I2CError>> {
+    /// Use previously saved coefficients instead of reading them from the sensor.
+    ///
+    /// Skips waiting for COEF_RDY on boot. Only PROD_ID is read to check that a supported
+    /// sensor is attached, the coefficients must come from this same sensor.
+    pub fn restore_calibration(
+        mut self,
+        coeffs: &CalibrationCoeffs,
+    ) -> Result<DPS3xx<B, Calibrated>, Error<E>> {
+        check_chip(self.chip_info()?)?;
+        if !coeffs.is_plausible() {
+            return Err(Error::InvalidCoefficients);
+        }
+        self.coeffs = *coeffs;
+
+        Ok(self.into_state())
+    }
+}
+
+impl<B, E, S> DPS3xx<B, S>
+where
+    B: Bus<Error = E>,
+{
+    pub fn status(&mut self) -> Result<Status, Error<E>> {
         let status = self.read_status()?;
         Ok(Status::from_bits(status))
     }
 
     /// Read status bits from MEAS_CFG reg.
-    /// MEAS_CFG register is masked with 0xF0
-    pub fn read_status(&mut self) -> Result<u8, Error<I2CError>> {
+    /// MEAS_CFG register is masked with 0xF0, see [`MeasCfg`] for all fields
+    pub fn read_status(&mut self) -> Result<u8, Error<E>> {
         let meas_cfg = self.read_reg(Register::MEAS_CFG)?;
         Ok(meas_cfg & 0xF0)
     }
 
+    /// Read and clear the INT_STS register
+    pub fn interrupt_status(&mut self) -> Result<InterruptStatus, Error<E>> {
+        let status = self.read_reg(Register::INT_STS)?;
+        Ok(InterruptStatus::from_bits(status))
+    }
+
     /// Returns true if sensor coeficients are available
-    pub fn coef_ready(&mut self) -> Result<bool, Error<I2CError>> {
+    pub fn coef_ready(&mut self) -> Result<bool, Error<E>> {
         Ok(self.status()?.coef_ready)
     }
 
     /// Returns true if sensor initialized and ready to take measurements
-    pub fn init_complete(&mut self) -> Result<bool, Error<I2CError>> {
+    pub fn init_complete(&mut self) -> Result<bool, Error<E>> {
         Ok(self.status()?.init_complete)
     }
 
     /// Returns true if temperature measurement is ready
-    pub fn temp_ready(&mut self) -> Result<bool, Error<I2CError>> {
+    pub fn temp_ready(&mut self) -> Result<bool, Error<E>> {
         Ok(self.status()?.temp_ready)
     }
 
     /// Returns true if pressure measurement is ready
-    pub fn pres_ready(&mut self) -> Result<bool, Error<I2CError>> {
+    pub fn pres_ready(&mut self) -> Result<bool, Error<E>> {
         Ok(self.status()?.pres_ready)
     }
 }
 
-impl<I2C, I2CError, S> DPS3xx<I2C, S>
+impl<B, E, S> DPS3xx<B, S>
 where
-    I2C: I2c<Error = I2CError>,
+    B: Bus<Error = E>,
     S: IsConfigured,
 {
-    pub fn start_measurement(&mut self, mode: MeasurementMode) -> Result<(), Error<I2CError>> {
-        if self.max_busy_time_exceeded(mode) {
+    pub fn start_measurement(&mut self, mode: MeasurementMode) -> Result<(), Error<E>> {
+        if max_busy_time_exceeded(&self.config, mode) {
             return Err(Error::BusyTimeExceeded);
         }
 
-        let mut meas_cfg: u8 = self.read_reg(Register::MEAS_CFG)?;
-        meas_cfg = (meas_cfg & 0xF8) | mode.meas_ctrl();
-
-        self.write_reg(Register::MEAS_CFG, meas_cfg)
+        self.modify_register(|meas_cfg: MeasCfg| MeasCfg {
+            mode: Some(mode),
+            ..meas_cfg
+        })
     }
 
     /// Read raw temperature contents
-    pub fn read_temp_raw(&mut self) -> Result<i32, Error<I2CError>> {
+    pub fn read_temp_raw(&mut self) -> Result<i32, Error<E>> {
         self.read_i24(Register::TMP_B2)
     }
 
     /// See section 4.9.2:
-    fn read_temp_scaled(&mut self) -> Result<f32, Error<I2CError>> {
+    fn read_temp_scaled(&mut self) -> Result<f32, Error<E>> {
         let temp_cfg = self.read_reg(Register::TEMP_CFG)?;
-        let osr = (temp_cfg & 0x07) as usize;
-        let raw_sc: f32 = self.read_temp_raw()? as f32
-            / SCALE_FACTORS
-                .get(osr)
-                .ok_or(Error::InvalidOversampling(temp_cfg))?;
-
-        Ok(raw_sc)
+        let temp_raw = self.read_temp_raw()?;
+        scale_raw(temp_raw, temp_cfg).ok_or(Error::InvalidOversampling(temp_cfg))
     }
 
     /// Read raw pressure contents
-    pub fn read_pressure_raw(&mut self) -> Result<i32, Error<I2CError>> {
+    pub fn read_pressure_raw(&mut self) -> Result<i32, Error<E>> {
         self.read_i24(Register::PSR_B2)
     }
 
-    fn read_pressure_scaled(&mut self) -> Result<f32, Error<I2CError>> {
+    fn read_pressure_scaled(&mut self) -> Result<f32, Error<E>> {
+        let prs_cfg = self.read_reg(Register::PRS_CFG)?;
+        let pres_raw = self.read_pressure_raw()?;
+        scale_raw(pres_raw, prs_cfg).ok_or(Error::InvalidOversampling(prs_cfg))
+    }
+
+    fn read_temp_scaled_fixed(&mut self) -> Result<i64, Error<E>> {
+        let temp_cfg = self.read_reg(Register::TEMP_CFG)?;
+        let temp_raw = self.read_temp_raw()?;
+        scale_raw_fixed(temp_raw, temp_cfg).ok_or(Error::InvalidOversampling(temp_cfg))
+    }
+
+    fn read_pressure_scaled_fixed(&mut self) -> Result<i64, Error<E>> {
         let prs_cfg = self.read_reg(Register::PRS_CFG)?;
-        let osr = (prs_cfg & 0x07) as usize;
         let pres_raw = self.read_pressure_raw()?;
-        let pres_scaled = pres_raw as f32
-            / SCALE_FACTORS
-                .get(osr)
-                .ok_or(Error::InvalidOversampling(prs_cfg))?;
+        scale_raw_fixed(pres_raw, prs_cfg).ok_or(Error::InvalidOversampling(prs_cfg))
+    }
+}
+
+impl<I2C, S> DPS3xx<I2cBus<I2C>, S> {
+    pub fn release(self) -> I2C {
+        self.bus.release()
+    }
+}
+
+impl<SPI, S> DPS3xx<SpiBus<SPI>, S> {
+    pub fn release(self) -> SPI {
+        self.bus.release()
+    }
+}
+
+impl<B, D, S> DPS3xx<RetryBus<B, D>, S> {
+    /// Retries and hard failures since creation or the last [`DPS3xx::reset_retry_stats`]
+    pub fn retry_stats(&self) -> RetryStats {
+        self.bus.stats()
+    }
 
-        Ok(pres_scaled)
+    pub fn reset_retry_stats(&mut self) {
+        self.bus.reset_stats();
     }
 }
 
-impl<I2C, I2CError> DPS3xx<I2C, Calibrated>
+impl<I2C, D, S> DPS3xx<RetryBus<I2cBus<I2C>, D>, S> {
+    pub fn release(self) -> (I2C, D) {
+        let (bus, delay) = self.bus.release();
+        (bus.release(), delay)
+    }
+}
+
+impl<SPI, D, S> DPS3xx<RetryBus<SpiBus<SPI>, D>, S> {
+    pub fn release(self) -> (SPI, D) {
+        let (bus, delay) = self.bus.release();
+        (bus.release(), delay)
+    }
+}
+
+impl<B, E> DPS3xx<B, Calibrated>
 where
-    I2C: I2c<Error = I2CError>,
+    B: Bus<Error = E>,
 {
+    /// Coefficients in use, e.g. to save with [`CalibrationCoeffs::to_blob`] for
+    /// [`DPS3xx::restore_calibration`]
+    pub fn calibration_coefficients(&self) -> CalibrationCoeffs {
+        self.coeffs
+    }
+
     /// Read calibrated temperature data in degrees Celsius.
     ///
     /// This method uses the pre calculated constants based on the calibration coefficients
     /// which have to be initialized with [Self::read_calibration_coefficients()] beforehand.
     ///
     /// See section 4.9.2 in the datasheet (formula), Sec 8.11 (coefficients)
-    pub fn read_temp_calibrated(&mut self) -> Result<f32, Error<I2CError>> {
+    pub fn read_temp_calibrated(&mut self) -> Result<f32, Error<E>> {
         let scaled = self.read_temp_scaled()?;
-        Ok((self.coeffs.C0 as f32 * 0.5) + (self.coeffs.C1 as f32 * scaled))
+        Ok(calibrate_temperature(&self.coeffs, scaled))
     }
 
-    pub fn try_read_temp_calibrated(&mut self) -> nb::Result<f32, Error<I2CError>> {
+    pub fn try_read_temp_calibrated(&mut self) -> nb::Result<f32, Error<E>> {
         if !self.temp_ready()? {
             return Err(nb::Error::WouldBlock);
         }
@@ -430,26 +609,482 @@ where
     ///
     /// See section 8.11 in the datasheet.
     /// See section 4.9.1 for calculation method.
-    pub fn read_pressure_calibrated(&mut self) -> Result<f32, Error<I2CError>> {
+    pub fn read_pressure_calibrated(&mut self) -> Result<f32, Error<E>> {
         let pres_scaled = self.read_pressure_scaled()?;
         let temp_scaled = self.read_temp_scaled()?;
         let pres_cal = calibrate_pressure(&self.coeffs, pres_scaled, temp_scaled);
         Ok(pres_cal)
     }
 
-    pub fn try_read_pressure_calibrated(&mut self) -> nb::Result<f32, Error<I2CError>> {
+    /// Integer-only version of [`Self::read_temp_calibrated`], for MCUs without FPU.
+    ///
+    /// Returns centi-degrees Celsius ([`crate::TEMPERATURE_FIXED_SCALE`]), within 0.01 degrees
+    /// of the `f32` result.
+    pub fn read_temp_fixed(&mut self) -> Result<i32, Error<E>> {
+        let scaled = self.read_temp_scaled_fixed()?;
+        Ok(calibrate_temperature_fixed(&self.coeffs, scaled))
+    }
+
+    /// Integer-only version of [`Self::read_pressure_calibrated`], for MCUs without FPU.
+    ///
+    /// Returns Pa multiplied by [`crate::PRESSURE_FIXED_SCALE`]. Stays within 0.25 Pa of the
+    /// `f32` result for any coefficient set.
+    pub fn read_pressure_fixed(&mut self) -> Result<i32, Error<E>> {
+        let pres_scaled = self.read_pressure_scaled_fixed()?;
+        let temp_scaled = self.read_temp_scaled_fixed()?;
+        Ok(calibrate_pressure_fixed(
+            &self.coeffs,
+            pres_scaled,
+            temp_scaled,
+        ))
+    }
+
+    pub fn try_read_pressure_calibrated(&mut self) -> nb::Result<f32, Error<E>> {
         if !self.pres_ready()? {
             return Err(nb::Error::WouldBlock);
         }
         self.read_pressure_calibrated().map_err(nb::Error::Other)
     }
+
+    /// Read pressure and temperature results (PSR_B2..TMP_B0) in a single bus transaction.
+    ///
+    /// Unlike [`Self::read_pressure_calibrated`], the oversampling scale factors are taken
+    /// from the stored [`Config`] instead of being read back from PRS_CFG/TEMP_CFG.
+    /// Compensated by [`RawDecoder`], so logged bursts decode to the same values offline.
+    pub fn read_sample(&mut self) -> Result<Measurement, Error<E>> {
+        let mut burst = [0; RAW_BURST_LEN];
+        self.bus.read_many(Register::PSR_B2, &mut burst)?;
+        let decoder = RawDecoder::from_coefficients(
+            self.coeffs,
+            self.config.pres_res.unwrap_or_default(),
+            self.config.temp_res.unwrap_or_default(),
+        );
+        Ok(decoder.measurement(&burst))
+    }
+
+    /// One-shot temperature in degrees Celsius, see [`Self::measure_both`].
+    pub fn measure_temperature<D>(&mut self, delay: &mut D) -> Result<f32, Error<E>>
+    where
+        D: DelayNs,
+    {
+        self.run_one_shot(MeasurementMode::OneShotTemperature, delay)?;
+        self.read_temp_calibrated()
+    }
+
+    /// One-shot pressure in Pa, see [`Self::measure_both`].
+    ///
+    /// Compensated with the last temperature result, which is stale or missing if no
+    /// temperature was measured recently.
+    pub fn measure_pressure<D>(&mut self, delay: &mut D) -> Result<f32, Error<E>>
+    where
+        D: DelayNs,
+    {
+        self.run_one_shot(MeasurementMode::OneShotPressure, delay)?;
+        self.read_pressure_calibrated()
+    }
+
+    /// One-shot temperature followed by one-shot pressure, as recommended in section 4.4.
+    ///
+    /// Each conversion waits [`calc_total_wait_ms`] for the configured oversampling, then
+    /// polls the ready flag for up to the same time again. Returns
+    /// [`Error::MeasurementTimeout`] with the sensor back in standby if the flag is not set.
+    /// A running background measurement is replaced.
+    pub fn measure_both<D>(&mut self, delay: &mut D) -> Result<Measurement, Error<E>>
+    where
+        D: DelayNs,
+    {
+        self.run_one_shot(MeasurementMode::OneShotTemperature, delay)?;
+        self.run_one_shot(MeasurementMode::OneShotPressure, delay)?;
+        self.read_sample()
+    }
+
+    fn run_one_shot<D>(&mut self, mode: MeasurementMode, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayNs,
+    {
+        if !self.one_shot_ready(mode, delay, true)? {
+            return Err(Error::MeasurementTimeout);
+        }
+        Ok(())
+    }
+
+    /// Start a one-shot conversion and wait [`calc_total_wait_ms`] for it. With `poll`, the
+    /// ready flag is polled for up to the same time again. Goes to standby if not ready.
+    fn one_shot_ready<D>(
+        &mut self,
+        mode: MeasurementMode,
+        delay: &mut D,
+        poll: bool,
+    ) -> Result<bool, Error<E>>
+    where
+        D: DelayNs,
+    {
+        let oversampling = match mode {
+            MeasurementMode::OneShotTemperature => self.config.temp_res.unwrap_or_default() as u8,
+            _ => self.config.pres_res.unwrap_or_default() as u8,
+        };
+        let wait_ms = calc_total_wait_ms(0, oversampling);
+        self.start_measurement(mode)?;
+        delay.delay_ms(wait_ms);
+
+        let mut remaining_ms = if poll { wait_ms } else { 0 };
+        loop {
+            let status = self.status()?;
+            let ready = match mode {
+                MeasurementMode::OneShotTemperature => status.temp_ready,
+                _ => status.pres_ready,
+            };
+            if ready {
+                return Ok(true);
+            }
+            if remaining_ms < MEASUREMENT_POLL_MS {
+                self.standby()?;
+                return Ok(false);
+            }
+            delay.delay_ms(MEASUREMENT_POLL_MS);
+            remaining_ms -= MEASUREMENT_POLL_MS;
+        }
+    }
+
+    /// [`Self::read_sample`] with the time it was measured at.
+    ///
+    /// In background mode the result is from the last finished conversion, so the read time is
+    /// backdated by the conversion time of the running mode: the temperature oversampling in
+    /// [`MeasurementMode::BackgroundTemperature`], the pressure oversampling otherwise. This
+    /// assumes the result is read right after it is ready, e.g. on the data-ready interrupt.
+    /// One-shot and idle reads get the time of the read. Reads MEAS_CFG in addition to the
+    /// burst.
+    pub fn read_sample_timestamped<C>(
+        &mut self,
+        clock: &mut C,
+    ) -> Result<Timestamped<Measurement>, Error<E>>
+    where
+        C: Clock,
+    {
+        let meas_cfg: MeasCfg = self.read_register()?;
+        let measurement = self.read_sample()?;
+        let read_us = clock.now_us();
+
+        let conversion_us = match meas_cfg.mode {
+            Some(MeasurementMode::BackgroundTemperature) => {
+                conversion_time_us(self.config.temp_res.unwrap_or_default() as u8)
+            }
+            Some(
+                MeasurementMode::BackgroundPressure
+                | MeasurementMode::BackgroundPressureAndTemperature,
+            ) => conversion_time_us(self.config.pres_res.unwrap_or_default() as u8),
+            Some(MeasurementMode::OneShotPressure | MeasurementMode::OneShotTemperature) | None => {
+                0
+            }
+        };
+        Ok(Timestamped {
+            timestamp_us: read_us.saturating_sub(conversion_us),
+            value: measurement,
+        })
+    }
+
+    /// Production pass/fail check, see [`SelfTestReport`].
+    ///
+    /// Runs one-shot conversions, including one with the other temperature sensor, so a
+    /// running measurement is stopped. TEMP_CFG is restored and the sensor is left in standby.
+    pub fn self_test<D>(&mut self, delay: &mut D) -> Result<SelfTestReport, Error<E>>
+    where
+        D: DelayNs,
+    {
+        let chip = self.chip_info()?;
+        self.standby()?;
+
+        let mut pres_raw = [None; SELF_TEST_SAMPLES];
+        let mut temp_raw = [None; SELF_TEST_SAMPLES];
+        for (pres, temp) in pres_raw.iter_mut().zip(temp_raw.iter_mut()) {
+            *temp = self.one_shot_raw(MeasurementMode::OneShotTemperature, delay)?;
+            *pres = self.one_shot_raw(MeasurementMode::OneShotPressure, delay)?;
+        }
+
+        let temp_cfg: TempCfg = self.read_register()?;
+        self.write_register(TempCfg {
+            external: !temp_cfg.external,
+            ..temp_cfg
+        })?;
+        let other_temp_raw = self.one_shot_raw(MeasurementMode::OneShotTemperature, delay);
+        self.write_register(temp_cfg)?;
+
+        let resolutions = (
+            self.config.pres_res.unwrap_or_default() as u8,
+            self.config.temp_res.unwrap_or_default() as u8,
+        );
+        Ok(SelfTestReport::evaluate(
+            chip,
+            &self.coeffs,
+            resolutions,
+            &pres_raw,
+            &temp_raw,
+            other_temp_raw?,
+        ))
+    }
+
+    /// Run a one-shot conversion, `None` if it is not ready after [`calc_total_wait_ms`]
+    fn one_shot_raw<D>(
+        &mut self,
+        mode: MeasurementMode,
+        delay: &mut D,
+    ) -> Result<Option<i32>, Error<E>>
+    where
+        D: DelayNs,
+    {
+        if !self.one_shot_ready(mode, delay, false)? {
+            return Ok(None);
+        }
+        let result = match mode {
+            MeasurementMode::OneShotTemperature => Register::TMP_B2,
+            _ => Register::PSR_B2,
+        };
+        Ok(Some(self.read_i24(result)?))
+    }
+
+    /// Read pressure and convert it to altitude above the `qnh_pa` reference level.
+    ///
+    /// See [`crate::altitude()`].
+    pub fn read_altitude(&mut self, qnh_pa: f32) -> Result<f32, Error<E>> {
+        let pressure = self.read_pressure_calibrated()?;
+        Ok(altitude(pressure, qnh_pa))
+    }
+
+    /// Like [`Self::read_altitude`], but corrected with the temperature measured by the sensor.
+    ///
+    /// See [`crate::altitude_temperature_corrected`].
+    pub fn read_altitude_temperature_corrected(&mut self, qnh_pa: f32) -> Result<f32, Error<E>> {
+        let pressure = self.read_pressure_calibrated()?;
+        let temperature = self.read_temp_calibrated()?;
+        Ok(altitude_temperature_corrected(
+            pressure,
+            qnh_pa,
+            temperature,
+        ))
+    }
+
+    /// Check the interrupt pin and, if it is at the active level set with [`Config::int_hl`],
+    /// read (and so clear) INT_STS and the compensated results it flags.
+    ///
+    /// Enable the interrupt sources with [`Config::int_pres`], [`Config::int_temp`] and
+    /// [`Config::fifo`].
+    pub fn try_read_interrupt<P>(&mut self, pin: &mut P) -> nb::Result<InterruptSample, Error<E>>
+    where
+        P: InputPin,
+    {
+        let asserted = if self.config.int_hl {
+            pin.is_high()
+        } else {
+            pin.is_low()
+        }
+        .map_err(|err| Error::PinError(digital::Error::kind(&err)))?;
+        if !asserted {
+            return Err(nb::Error::WouldBlock);
+        }
+        self.read_interrupt_sample().map_err(nb::Error::Other)
+    }
+
+    fn read_interrupt_sample(&mut self) -> Result<InterruptSample, Error<E>> {
+        let status = self.interrupt_status()?;
+        let pressure = if status.pres_ready {
+            Some(self.read_pressure_calibrated()?)
+        } else {
+            None
+        };
+        let temperature = if status.temp_ready {
+            Some(self.read_temp_calibrated()?)
+        } else {
+            None
+        };
+        Ok(InterruptSample {
+            status,
+            pressure,
+            temperature,
+        })
+    }
+
+    /// Read FIFO fill state from FIFO_STS register
+    pub fn fifo_status(&mut self) -> Result<FifoStatus, Error<E>> {
+        let status = self.read_reg(Register::FIFO_STS)?;
+        Ok(FifoStatus::from_bits(status))
+    }
+
+    /// Drain up to `out.len()` (at most [`FIFO_SIZE`]) raw FIFO entries in a single burst read.
+    ///
+    /// Returns the number of entries read before the first [`FifoEntry::Empty`] marker,
+    /// entries after it are left as [`FifoEntry::Empty`].
+    /// FIFO has to be enabled with [`Config::fifo`] and the sensor running in background mode.
+    pub fn read_fifo_raw(&mut self, out: &mut [FifoEntry]) -> Result<usize, Error<E>> {
+        let len = out.len().min(FIFO_SIZE);
+        let mut bytes = [0u8; FIFO_SIZE * 3];
+        let (bytes, _) = bytes.split_at_mut(len * 3);
+        self.bus.read_many(Register::PSR_B2, bytes)?;
+
+        let mut count = 0;
+        let mut empty = false;
+        for (entry, raw) in out.iter_mut().zip(bytes.chunks_exact(3)) {
+            let &[b2, b1, b0] = raw else { break };
+            *entry = if empty {
+                FifoEntry::Empty
+            } else {
+                FifoEntry::from_raw(i24_from_bytes([b2, b1, b0]))
+            };
+            if *entry == FifoEntry::Empty {
+                empty = true;
+            } else {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Drain the FIFO like [`Self::read_fifo_raw`] and compensate the results.
+    ///
+    /// Pressure entries are compensated with the closest preceding temperature entry of the
+    /// same burst, or the first one if none precedes it. If the burst holds no temperature at all
+    /// (pressure only background mode), the temperature result register is used.
+    /// Scale factors come from the stored [`Config`].
+    pub fn read_fifo(&mut self, out: &mut [FifoSample]) -> Result<usize, Error<E>> {
+        let mut entries = [FifoEntry::Empty; FIFO_SIZE];
+        let (entries, _) = entries.split_at_mut(out.len().min(FIFO_SIZE));
+        let count = self.read_fifo_raw(entries)?;
+
+        let prs_cfg = self.config.pres_res.unwrap_or_default() as u8;
+        let temp_cfg = self.config.temp_res.unwrap_or_default() as u8;
+        let scale_temp =
+            |raw: i32| scale_raw(raw, temp_cfg).ok_or(Error::InvalidOversampling(temp_cfg));
+
+        let mut temp_scaled = None;
+        for (entry, sample) in entries.iter().zip(out.iter_mut()).take(count) {
+            *sample = match *entry {
+                FifoEntry::Temperature(raw) => {
+                    let scaled = scale_temp(raw)?;
+                    temp_scaled = Some(scaled);
+                    FifoSample::Temperature(calibrate_temperature(&self.coeffs, scaled))
+                }
+                FifoEntry::Pressure(raw) => {
+                    let temp = match temp_scaled {
+                        Some(temp) => temp,
+                        None => {
+                            let first_temp = entries.iter().find_map(|entry| match entry {
+                                FifoEntry::Temperature(raw) => Some(*raw),
+                                _ => None,
+                            });
+                            let temp = match first_temp {
+                                Some(raw) => scale_temp(raw)?,
+                                None => self.read_temp_scaled()?,
+                            };
+                            temp_scaled = Some(temp);
+                            temp
+                        }
+                    };
+                    let scaled =
+                        scale_raw(raw, prs_cfg).ok_or(Error::InvalidOversampling(prs_cfg))?;
+                    FifoSample::Pressure(calibrate_pressure(&self.coeffs, scaled, temp))
+                }
+                FifoEntry::Empty => break,
+            };
+        }
+        Ok(count)
+    }
+
+    /// [`Self::read_fifo`] with the time each result was measured at.
+    ///
+    /// The newest pressure and temperature are taken to have finished just before the read,
+    /// older ones are spaced by the configured [`crate::PressureRate`] and
+    /// [`crate::TemperatureRate`]. Drain the FIFO before it is full, entries that did not fit
+    /// would break the spacing.
+    pub fn read_fifo_timestamped<C>(
+        &mut self,
+        clock: &mut C,
+        out: &mut [Timestamped<FifoSample>],
+    ) -> Result<usize, Error<E>>
+    where
+        C: Clock,
+    {
+        let mut samples = [FifoSample::Pressure(0.0); FIFO_SIZE];
+        let (samples, _) = samples.split_at_mut(out.len().min(FIFO_SIZE));
+        let count = self.read_fifo(samples)?;
+        let read_us = clock.now_us();
+
+        let (samples, _) = samples.split_at(count);
+        fifo_timestamps(&self.config, read_us, samples, out);
+        Ok(count)
+    }
+
+    /// Change rates, resolutions and interrupt/FIFO settings without losing calibration.
+    ///
+    /// A running measurement is stopped first and has to be restarted with
+    /// [`Self::start_measurement`]. Only PRS_CFG, TEMP_CFG and CFG_REG values that differ
+    /// are written, with P_SHIFT/T_SHIFT derived as on init. Without touching the device,
+    /// returns [`Error::InvalidConfig`] for the same checks as [`DPS3xx::new`], and
+    /// [`Error::BusyTimeExceeded`] if the running background mode would exceed the busy-time
+    /// limit with the new config.
+    pub fn reconfigure(&mut self, config: &Config) -> Result<(), Error<E>> {
+        config
+            .check(None, B::SPI && !config.spi_mode)
+            .map_err(Error::InvalidConfig)?;
+        let meas_cfg: MeasCfg = self.read_register()?;
+        if let Some(mode) = meas_cfg.mode {
+            if max_busy_time_exceeded(config, mode) {
+                return Err(Error::BusyTimeExceeded);
+            }
+            self.standby()?;
+        }
+
+        let prs_cfg = self.read_reg(Register::PRS_CFG)?;
+        let new_prs_cfg = prs_cfg_value(prs_cfg, config);
+        if new_prs_cfg != prs_cfg {
+            self.write_reg(Register::PRS_CFG, new_prs_cfg)?;
+        }
+
+        let temp_cfg = self.read_reg(Register::TEMP_CFG)?;
+        let new_temp_cfg = tmp_cfg_value(temp_cfg, config, None);
+        if new_temp_cfg != temp_cfg {
+            self.write_reg(Register::TEMP_CFG, new_temp_cfg)?;
+        }
+
+        let cfg = self.read_reg(Register::CFG_REG)?;
+        let new_cfg = cfg_reg_value(config);
+        if new_cfg != cfg {
+            self.write_reg(Register::CFG_REG, new_cfg)?;
+        }
+
+        self.config = *config;
+        Ok(())
+    }
+
+    /// Soft reset the sensor and bring it back to [`Calibrated`].
+    ///
+    /// Waits for the post-reset start-up time, re-applies the stored [`Config`] and the
+    /// temperature workaround, and verifies INIT_RDY. The calibration coefficients are kept,
+    /// as they are fixed per chip. Shares [`Config::init_timeout_ms`] with
+    /// [`DPS3xx::init_and_calibrate`], a stall is reported as [`Error::InitTimeout`].
+    pub fn reset_and_reinit<D>(mut self, delay: &mut D) -> Result<DPS3xx<B, Calibrated>, Error<E>>
+    where
+        D: DelayNs,
+    {
+        let mut budget = InitBudget::new(&self.config);
+        self.write_register(ResetReg::SOFT_RESET)?;
+        budget.take(RESET_WAIT_MS, InitStage::WaitingInitComplete)?;
+        delay.delay_ms(RESET_WAIT_MS);
+
+        let dps = self.into_state::<Unconfigured>().start_init()?;
+        let dps = dps.wait_init_complete(delay, &mut budget)?;
+        Ok(dps.into_state())
+    }
+
+    /// Empty the FIFO through the FIFO_FLUSH bit, without a soft reset
+    pub fn flush_fifo(&mut self) -> Result<(), Error<E>> {
+        self.write_register(ResetReg::FLUSH_FIFO)
+    }
 }
 
-impl<I2C, S, I2CError> DPS3xx<I2C, S>
+impl<B, S, E> DPS3xx<B, S>
 where
-    I2C: I2c<Error = I2CError>,
+    B: Bus<Error = E>,
 {
-    fn apply_config(&mut self) -> Result<(), Error<I2CError>> {
+    fn apply_config(&mut self) -> Result<(), Error<E>> {
         let config = self.config;
         let prs_cfg = self.read_reg(Register::PRS_CFG)?;
 
@@ -457,22 +1092,16 @@ where
 
         self.write_reg(Register::PRS_CFG, new_prs_cfg)?;
 
-        let temp_res = config.temp_res.unwrap_or_default() as u8;
-        let pressure_res = config.pres_res.unwrap_or_default() as u8;
-
         let temp_cfg = self.read_reg(Register::TEMP_CFG)?;
         let coef_source = if config.temp_ext.is_none() {
-            Some((self.read_reg(Register::TMP_COEF_SRCE)? & 0x80) != 0)
+            Some(self.read_register::<TmpCoefSrce>()?.external)
         } else {
             None
         };
         let new_temp_cfg = tmp_cfg_value(temp_cfg, &config, coef_source);
         self.write_reg(Register::TEMP_CFG, new_temp_cfg)?;
 
-        let temp_shift = config.temp_shift || temp_res > TemperatureResolution::_8_SAMPLES as u8;
-        let pres_shift = config.pres_shift || pressure_res > PressureResolution::_8_SAMPLES as u8;
-
-        let cfg = cfg_reg_value(&config, temp_shift, pres_shift);
+        let cfg = cfg_reg_value(&config);
 
         self.write_reg(Register::CFG_REG, cfg)?;
 
@@ -480,65 +1109,114 @@ where
     }
 
     /// Set measurement mode to `idle`
-    fn standby(&mut self) -> Result<(), Error<I2CError>> {
+    fn standby(&mut self) -> Result<(), Error<E>> {
         self.write_reg(Register::MEAS_CFG, 0)
     }
 
     /// Returns the product ID from PROD_ID register.
     /// This value is expected to be 0x10
-    pub fn get_product_id(&mut self) -> Result<u8, Error<I2CError>> {
+    pub fn get_product_id(&mut self) -> Result<u8, Error<E>> {
         self.read_reg(Register::PROD_ID)
     }
 
-    /// Issue a full reset and fifo flush
-    pub fn reset(mut self) -> Result<DPS3xx<I2C, Unconfigured>, Error<I2CError>> {
-        self.write_reg(Register::RESET, 0b10001001)?;
-        self.init_ready = false;
-        self.init_temp_started = false;
+    /// Read PROD_ID and decode product, revision and variant
+    pub fn chip_info(&mut self) -> Result<ChipInfo, Error<E>> {
+        let id = self.get_product_id()?;
+        Ok(ChipInfo::from_id(id))
+    }
+
+    /// Issue a full reset and fifo flush.
+    ///
+    /// Returns immediately, see [`DPS3xx::reset_and_reinit`] to wait for the sensor and keep
+    /// the calibration.
+    pub fn reset(mut self) -> Result<DPS3xx<B, Unconfigured>, Error<E>> {
+        self.write_register(ResetReg::SOFT_RESET)?;
+        self.init = InitState::default();
 
         Ok(self.into_state())
     }
 
-    fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), Error<I2CError>> {
+    /// Read every documented register, PSR_B2 through TMP_COEF_SRCE, in any state.
+    ///
+    /// Meant for diagnostics: reading clears INT_STS, and with the FIFO enabled it pops one
+    /// FIFO entry. For a dump after an init stall, see [`DPS3xx::init_and_calibrate`].
+    pub fn register_dump(&mut self) -> Result<RegisterDump, Error<E>> {
+        let mut block = [0; DUMP_BLOCK_LEN];
+        self.bus.read_many(Register::PSR_B2, &mut block)?;
+        let mut coeffs = [0; COEFFS_LEN];
+        self.bus.read_many(Register::COEFF_REG_1, &mut coeffs)?;
+        let tmp_coef_srce = self.read_reg(Register::TMP_COEF_SRCE)?;
+        Ok(RegisterDump::from_bytes(&block, &coeffs, tmp_coef_srce))
+    }
+
+    /// Read a register as its typed contents, e.g. `dps.read_register::<CfgReg>()`.
+    ///
+    /// Reading [`InterruptStatus`] clears it.
+    pub fn read_register<R: ReadableRegister>(&mut self) -> Result<R, Error<E>> {
+        Ok(R::from_bits(self.read_reg(R::REGISTER)?))
+    }
+
+    /// Write a register from its typed contents.
+    ///
+    /// Bypasses the stored [`Config`], prefer [`DPS3xx::reconfigure`] for configuration changes.
+    pub fn write_register<R: WritableRegister>(&mut self, value: R) -> Result<(), Error<E>> {
+        self.write_reg(R::REGISTER, value.into_bits())
+    }
+
+    /// Read a register, change it with `f` and write it back
+    pub fn modify_register<R, F>(&mut self, f: F) -> Result<(), Error<E>>
+    where
+        R: ReadableRegister + WritableRegister,
+        F: FnOnce(R) -> R,
+    {
+        let value = self.read_register::<R>()?;
+        self.write_register(f(value))
+    }
+
+    fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), Error<E>> {
         self.bus.write_reg(reg, value)?;
         Ok(())
     }
 
-    fn read_reg(&mut self, reg: Register) -> Result<u8, Error<I2CError>> {
+    fn read_reg(&mut self, reg: Register) -> Result<u8, Error<E>> {
         Ok(self.bus.read_reg(reg)?)
     }
 
-    fn write_addr(&mut self, addr: u8, value: u8) -> Result<(), Error<I2CError>> {
+    fn write_addr(&mut self, addr: u8, value: u8) -> Result<(), Error<E>> {
         self.bus.write_addr(addr, value)?;
         Ok(())
     }
-    /// Taken from official Arduino library.
-    // Fix IC with a fuse bit problem, which lead to a wrong temperature
-    // Should not affect ICs without this problem
-    fn apply_temp_workaround_registers(&mut self) -> Result<(), Error<I2CError>> {
-        self.write_addr(0x0E, 0xA5)?;
-        self.write_addr(0x0F, 0x96)?;
-        self.write_addr(0x62, 0x02)?;
-        self.write_addr(0x0E, 0x00)?;
-        self.write_addr(0x0F, 0x00)?;
+    /// Taken from official Arduino library, see [`TEMP_WORKAROUND_WRITES`].
+    fn apply_temp_workaround_registers(&mut self) -> Result<(), Error<E>> {
+        for (addr, value) in TEMP_WORKAROUND_WRITES {
+            self.write_addr(addr, value)?;
+        }
         Ok(())
     }
 
-    fn into_state<T>(self) -> DPS3xx<I2C, T> {
+    /// Fresh [`Config::init_timeout_ms`] budget, for [`crate::DPS3xxManager`]
+    pub(crate) fn init_budget(&self) -> InitBudget {
+        InitBudget::new(&self.config)
+    }
+
+    /// Back to [`Unconfigured`], keeping bus and config, for [`crate::DPS3xxManager`]
+    pub(crate) fn into_unconfigured(self) -> DPS3xx<B, Unconfigured> {
+        self.into_state()
+    }
+
+    fn into_state<T>(self) -> DPS3xx<B, T> {
         DPS3xx {
             bus: self.bus,
             coeffs: self.coeffs,
             config: self.config,
-            init_ready: self.init_ready,
-            init_temp_started: self.init_temp_started,
+            init: self.init,
             _state: PhantomData,
         }
     }
 
-    fn read_i24(&mut self, reg: Register) -> Result<i32, Error<I2CError>> {
+    fn read_i24(&mut self, reg: Register) -> Result<i32, Error<E>> {
         let mut bytes: [u8; 3] = [0, 0, 0];
         self.bus.read_many(reg, &mut bytes)?;
-        let value = ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | (bytes[2] as u32);
-        Ok(get_twos_complement(value, 24))
+        Ok(i24_from_bytes(bytes))
     }
 }
@@ -0,0 +1,454 @@
+use crate::bitfields::{MeasCfg, ReadableRegister, ResetReg, TmpCoefSrce, WritableRegister};
+use crate::bus::{AsyncBus, I2cBus};
+use crate::calibration::{
+    calibrate_pressure, calibrate_temperature, CalibrationCoeffs, COEFFS_LEN,
+};
+use crate::chip::ChipInfo;
+use crate::config::Config;
+use crate::device::{
+    Calibrated, Configured, Error, InitInProgress, InitPoll, InitStage, IsConfigured,
+    MeasurementMode, Status, Unconfigured,
+};
+use crate::device_internal::{
+    cfg_reg_value, i24_from_bytes, init_wait_ms, max_busy_time_exceeded, prs_cfg_value, scale_raw,
+    tmp_cfg_value, TEMP_WORKAROUND_WRITES,
+};
+use crate::dump::{RegisterDump, DUMP_BLOCK_LEN};
+use crate::init::{
+    apply_temp_workaround, check_chip, check_coefficients, coefficient_retry, InitBudget,
+    InitState, InitStep, COEF_POLL_MS,
+};
+use crate::interrupt::{InterruptSample, InterruptStatus};
+use crate::register::Register;
+use core::marker::PhantomData;
+use embedded_hal::digital;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::i2c::I2c;
+
+/// Async version of [`crate::DPS3xx`], for executors such as Embassy.
+///
+/// Goes through the same typestates as the blocking driver and shares its init logic, but only
+/// covers the init, status, register and basic measurement methods.
+pub struct DPS3xxAsync<B, S> {
+    bus: B,
+    coeffs: CalibrationCoeffs,
+    config: Config,
+    init: InitState,
+    _state: PhantomData<S>,
+}
+
+/// [`DPS3xxAsync`] on I2C
+pub type DPS3xxAsyncI2c<I2C, S> = DPS3xxAsync<I2cBus<I2C>, S>;
+
+impl<I2C, E> DPS3xxAsync<I2cBus<I2C>, Unconfigured>
+where
+    I2C: I2c<Error = E>,
+{
+    pub fn new(i2c: I2C, address: u8, config: &Config) -> Result<Self, Error<E>> {
+        config.check(None, false).map_err(Error::InvalidConfig)?;
+        Ok(Self {
+            bus: I2cBus::new(i2c, address),
+            coeffs: CalibrationCoeffs::default(),
+            config: *config,
+            init: InitState::default(),
+            _state: PhantomData,
+        })
+    }
+}
+
+impl<I2C, S> DPS3xxAsync<I2cBus<I2C>, S> {
+    pub fn release(self) -> I2C {
+        self.bus.release()
+    }
+}
+
+impl<B, E> DPS3xxAsync<B, Unconfigured>
+where
+    B: AsyncBus<Error = E>,
+{
+    pub async fn start_init(mut self) -> Result<DPS3xxAsync<B, InitInProgress>, Error<E>> {
+        let chip = self.chip_info().await?;
+        check_chip(chip)?;
+        self.apply_config().await?;
+        self.standby().await?;
+
+        if apply_temp_workaround(&self.config, chip) {
+            self.apply_temp_workaround_registers().await?;
+        }
+
+        self.init = InitState::default();
+
+        Ok(self.into_state())
+    }
+
+    /// Same as [`crate::DPS3xx::init_and_calibrate`], but yields to the executor while waiting.
+    pub async fn init_and_calibrate<D>(
+        self,
+        delay: &mut D,
+    ) -> Result<DPS3xxAsync<B, Calibrated>, Error<E>>
+    where
+        D: DelayNs,
+    {
+        let mut dps = self.start_init().await?;
+        let mut budget = InitBudget::new(&dps.config);
+
+        let mut dps = loop {
+            match dps.poll_init().await? {
+                InitPoll::Pending(wait_ms) => {
+                    budget.take(wait_ms, dps.init.stage())?;
+                    delay.delay_ms(wait_ms).await;
+                }
+                InitPoll::Ready => match dps.finish_init() {
+                    Ok(dps) => break dps,
+                    Err(unfinished) => dps = unfinished,
+                },
+            }
+        };
+
+        while !dps.coef_ready().await? {
+            budget.take(COEF_POLL_MS, InitStage::WaitingCoefReady)?;
+            delay.delay_ms(COEF_POLL_MS).await;
+        }
+
+        dps.read_calibration_coefficients_unchecked().await
+    }
+}
+
+impl<B, E> DPS3xxAsync<B, InitInProgress>
+where
+    B: AsyncBus<Error = E>,
+{
+    pub async fn poll_init(&mut self) -> Result<InitPoll, Error<E>> {
+        if self.init.ready {
+            return Ok(InitPoll::Ready);
+        }
+
+        let status = self.status().await?;
+        match self.init.step(status) {
+            InitStep::Wait => {}
+            InitStep::StartTemperature => {
+                self.write_reg(
+                    Register::MEAS_CFG,
+                    MeasurementMode::OneShotTemperature.meas_ctrl(),
+                )
+                .await?;
+                self.init.temp_started = true;
+            }
+            InitStep::FinishTemperature => {
+                let _ = self.read_i24(Register::TMP_B2).await?;
+                self.standby().await?;
+                self.init = InitState {
+                    ready: true,
+                    temp_started: false,
+                };
+                return Ok(InitPoll::Ready);
+            }
+        }
+        Ok(InitPoll::Pending(init_wait_ms(&self.config)))
+    }
+
+    pub fn finish_init(self) -> Result<DPS3xxAsync<B, Configured>, Self> {
+        if self.init.ready {
+            Ok(self.into_state())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<B, E> DPS3xxAsync<B, Configured>
+where
+    B: AsyncBus<Error = E>,
+{
+    /// Read calibration coefficients. User must wait for `Self::coef_ready()` to return true before reading coefficients.
+    ///
+    /// See Sec 8.11
+    pub async fn read_calibration_coefficients(
+        mut self,
+    ) -> Result<DPS3xxAsync<B, Calibrated>, Error<E>> {
+        if !self.coef_ready().await? {
+            return Err(Error::CoefficientsNotReady);
+        }
+
+        self.read_calibration_coefficients_unchecked().await
+    }
+
+    /// Read and check the coefficient block, re-reading up to `Config::coeff_retries` times
+    async fn read_calibration_coefficients_unchecked(
+        mut self,
+    ) -> Result<DPS3xxAsync<B, Calibrated>, Error<E>> {
+        let mut retries = self.config.coeff_retries;
+        loop {
+            match self.read_checked_coefficients().await {
+                Ok(coeffs) => {
+                    self.coeffs = coeffs;
+                    return Ok(self.into_state());
+                }
+                Err(err) if coefficient_retry(&mut retries, &err) => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    async fn read_checked_coefficients(&mut self) -> Result<CalibrationCoeffs, Error<E>> {
+        let mut bytes = [0; COEFFS_LEN];
+        self.bus
+            .read_many(Register::COEFF_REG_1, &mut bytes)
+            .await?;
+        if !self.config.coeff_double_read {
+            return check_coefficients(&bytes, None);
+        }
+        let mut again = [0; COEFFS_LEN];
+        self.bus
+            .read_many(Register::COEFF_REG_1, &mut again)
+            .await?;
+        check_coefficients(&bytes, Some(&again))
+    }
+}
+
+impl<B, E, S> DPS3xxAsync<B, S>
+where
+    B: AsyncBus<Error = E>,
+{
+    pub async fn status(&mut self) -> Result<Status, Error<E>> {
+        let status = self.read_status().await?;
+        Ok(Status::from_bits(status))
+    }
+
+    /// Read status bits from MEAS_CFG reg.
+    /// MEAS_CFG register is masked with 0xF0
+    pub async fn read_status(&mut self) -> Result<u8, Error<E>> {
+        let meas_cfg = self.read_reg(Register::MEAS_CFG).await?;
+        Ok(meas_cfg & 0xF0)
+    }
+
+    /// Read and clear the INT_STS register
+    pub async fn interrupt_status(&mut self) -> Result<InterruptStatus, Error<E>> {
+        let status = self.read_reg(Register::INT_STS).await?;
+        Ok(InterruptStatus::from_bits(status))
+    }
+
+    /// Returns true if sensor coeficients are available
+    pub async fn coef_ready(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.status().await?.coef_ready)
+    }
+
+    /// Returns true if sensor initialized and ready to take measurements
+    pub async fn init_complete(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.status().await?.init_complete)
+    }
+
+    /// Returns true if temperature measurement is ready
+    pub async fn temp_ready(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.status().await?.temp_ready)
+    }
+
+    /// Returns true if pressure measurement is ready
+    pub async fn pres_ready(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.status().await?.pres_ready)
+    }
+
+    /// Returns the product ID from PROD_ID register.
+    /// This value is expected to be 0x10
+    pub async fn get_product_id(&mut self) -> Result<u8, Error<E>> {
+        self.read_reg(Register::PROD_ID).await
+    }
+
+    /// Read PROD_ID and decode product, revision and variant
+    pub async fn chip_info(&mut self) -> Result<ChipInfo, Error<E>> {
+        let id = self.get_product_id().await?;
+        Ok(ChipInfo::from_id(id))
+    }
+
+    /// Issue a full reset and fifo flush
+    pub async fn reset(mut self) -> Result<DPS3xxAsync<B, Unconfigured>, Error<E>> {
+        self.write_register(ResetReg::SOFT_RESET).await?;
+        self.init = InitState::default();
+
+        Ok(self.into_state())
+    }
+
+    async fn apply_config(&mut self) -> Result<(), Error<E>> {
+        let config = self.config;
+        let prs_cfg = self.read_reg(Register::PRS_CFG).await?;
+        self.write_reg(Register::PRS_CFG, prs_cfg_value(prs_cfg, &config))
+            .await?;
+
+        let temp_cfg = self.read_reg(Register::TEMP_CFG).await?;
+        let coef_source = if config.temp_ext.is_none() {
+            Some(self.read_register::<TmpCoefSrce>().await?.external)
+        } else {
+            None
+        };
+        let new_temp_cfg = tmp_cfg_value(temp_cfg, &config, coef_source);
+        self.write_reg(Register::TEMP_CFG, new_temp_cfg).await?;
+
+        let cfg = cfg_reg_value(&config);
+        self.write_reg(Register::CFG_REG, cfg).await
+    }
+
+    /// Set measurement mode to `idle`
+    async fn standby(&mut self) -> Result<(), Error<E>> {
+        self.write_reg(Register::MEAS_CFG, 0).await
+    }
+
+    async fn apply_temp_workaround_registers(&mut self) -> Result<(), Error<E>> {
+        for (addr, value) in TEMP_WORKAROUND_WRITES {
+            self.bus.write_addr(addr, value).await?;
+        }
+        Ok(())
+    }
+
+    /// Read every documented register, see [`crate::DPS3xx::register_dump`]
+    pub async fn register_dump(&mut self) -> Result<RegisterDump, Error<E>> {
+        let mut block = [0; DUMP_BLOCK_LEN];
+        self.bus.read_many(Register::PSR_B2, &mut block).await?;
+        let mut coeffs = [0; COEFFS_LEN];
+        self.bus
+            .read_many(Register::COEFF_REG_1, &mut coeffs)
+            .await?;
+        let tmp_coef_srce = self.read_reg(Register::TMP_COEF_SRCE).await?;
+        Ok(RegisterDump::from_bytes(&block, &coeffs, tmp_coef_srce))
+    }
+
+    /// Read a register as its typed contents, see [`crate::DPS3xx::read_register`]
+    pub async fn read_register<R: ReadableRegister>(&mut self) -> Result<R, Error<E>> {
+        Ok(R::from_bits(self.read_reg(R::REGISTER).await?))
+    }
+
+    /// Write a register from its typed contents, see [`crate::DPS3xx::write_register`]
+    pub async fn write_register<R: WritableRegister>(&mut self, value: R) -> Result<(), Error<E>> {
+        self.write_reg(R::REGISTER, value.into_bits()).await
+    }
+
+    /// Read a register, change it with `f` and write it back
+    pub async fn modify_register<R, F>(&mut self, f: F) -> Result<(), Error<E>>
+    where
+        R: ReadableRegister + WritableRegister,
+        F: FnOnce(R) -> R,
+    {
+        let value = self.read_register::<R>().await?;
+        self.write_register(f(value)).await
+    }
+
+    async fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), Error<E>> {
+        self.bus.write_reg(reg, value).await?;
+        Ok(())
+    }
+
+    async fn read_reg(&mut self, reg: Register) -> Result<u8, Error<E>> {
+        Ok(self.bus.read_reg(reg).await?)
+    }
+
+    async fn read_i24(&mut self, reg: Register) -> Result<i32, Error<E>> {
+        let mut bytes: [u8; 3] = [0, 0, 0];
+        self.bus.read_many(reg, &mut bytes).await?;
+        Ok(i24_from_bytes(bytes))
+    }
+
+    fn into_state<T>(self) -> DPS3xxAsync<B, T> {
+        DPS3xxAsync {
+            bus: self.bus,
+            coeffs: self.coeffs,
+            config: self.config,
+            init: self.init,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<B, E, S> DPS3xxAsync<B, S>
+where
+    B: AsyncBus<Error = E>,
+    S: IsConfigured,
+{
+    pub async fn start_measurement(&mut self, mode: MeasurementMode) -> Result<(), Error<E>> {
+        if max_busy_time_exceeded(&self.config, mode) {
+            return Err(Error::BusyTimeExceeded);
+        }
+
+        let meas_cfg: MeasCfg = self.read_register().await?;
+        self.write_register(MeasCfg {
+            mode: Some(mode),
+            ..meas_cfg
+        })
+        .await
+    }
+
+    /// Read raw temperature contents
+    pub async fn read_temp_raw(&mut self) -> Result<i32, Error<E>> {
+        self.read_i24(Register::TMP_B2).await
+    }
+
+    async fn read_temp_scaled(&mut self) -> Result<f32, Error<E>> {
+        let temp_cfg = self.read_reg(Register::TEMP_CFG).await?;
+        let temp_raw = self.read_temp_raw().await?;
+        scale_raw(temp_raw, temp_cfg).ok_or(Error::InvalidOversampling(temp_cfg))
+    }
+
+    /// Read raw pressure contents
+    pub async fn read_pressure_raw(&mut self) -> Result<i32, Error<E>> {
+        self.read_i24(Register::PSR_B2).await
+    }
+
+    async fn read_pressure_scaled(&mut self) -> Result<f32, Error<E>> {
+        let prs_cfg = self.read_reg(Register::PRS_CFG).await?;
+        let pres_raw = self.read_pressure_raw().await?;
+        scale_raw(pres_raw, prs_cfg).ok_or(Error::InvalidOversampling(prs_cfg))
+    }
+}
+
+impl<B, E> DPS3xxAsync<B, Calibrated>
+where
+    B: AsyncBus<Error = E>,
+{
+    /// Read calibrated temperature data in degrees Celsius.
+    ///
+    /// See section 4.9.2 in the datasheet (formula), Sec 8.11 (coefficients)
+    pub async fn read_temp_calibrated(&mut self) -> Result<f32, Error<E>> {
+        let scaled = self.read_temp_scaled().await?;
+        Ok(calibrate_temperature(&self.coeffs, scaled))
+    }
+
+    /// Read calibrated pressure data in Pa.
+    ///
+    /// See section 8.11 in the datasheet.
+    /// See section 4.9.1 for calculation method.
+    pub async fn read_pressure_calibrated(&mut self) -> Result<f32, Error<E>> {
+        let pres_scaled = self.read_pressure_scaled().await?;
+        let temp_scaled = self.read_temp_scaled().await?;
+        Ok(calibrate_pressure(&self.coeffs, pres_scaled, temp_scaled))
+    }
+
+    /// Wait for the interrupt pin to reach the active level set with [`Config::int_hl`],
+    /// then read (and so clear) INT_STS and the compensated results it flags.
+    pub async fn wait_for_interrupt<P>(&mut self, pin: &mut P) -> Result<InterruptSample, Error<E>>
+    where
+        P: Wait,
+    {
+        if self.config.int_hl {
+            pin.wait_for_high().await
+        } else {
+            pin.wait_for_low().await
+        }
+        .map_err(|err| Error::PinError(digital::Error::kind(&err)))?;
+
+        let status = self.interrupt_status().await?;
+        let pressure = if status.pres_ready {
+            Some(self.read_pressure_calibrated().await?)
+        } else {
+            None
+        };
+        let temperature = if status.temp_ready {
+            Some(self.read_temp_calibrated().await?)
+        } else {
+            None
+        };
+        Ok(InterruptSample {
+            status,
+            pressure,
+            temperature,
+        })
+    }
+}
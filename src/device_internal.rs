@@ -1,18 +1,38 @@
-use crate::config::Config;
+use crate::bitfields::{CfgReg, PrsCfg, TempCfg};
+use crate::calibration::{get_twos_complement, FIXED_FRAC_BITS};
+use crate::config::{Config, PressureResolution, TemperatureResolution};
+use crate::device::MeasurementMode;
 
-pub(crate) const PRODUCT_ID: u8 = 0x10;
+/// Start-up time after a soft reset before the serial interface responds
+pub(crate) const RESET_WAIT_MS: u32 = 12;
+/// Interval between ready flag polls once a one-shot conversion is overdue
+pub(crate) const MEASUREMENT_POLL_MS: u32 = 1;
 pub const BUSYTIME_SCALING: u32 = 10;
 pub const BUSYTIME_FAILSAFE_MS: u32 = 10;
 pub const MAX_BUSYTIME_UNITS: u32 = (1000 - BUSYTIME_FAILSAFE_MS) * BUSYTIME_SCALING;
+pub(crate) const SCALE_FACTORS_INT: [i32; 8] = [
+    524_288, 1_572_864, 3_670_016, 7_864_320, 253_952, 516_096, 1_040_384, 2_088_960,
+];
 pub(crate) const SCALE_FACTORS: [f32; 8] = [
-    524_288_f32,
-    1_572_864_f32,
-    3_670_016_f32,
-    7_864_320_f32,
-    253_952_f32,
-    516_096_f32,
-    1_040_384_f32,
-    2_088_960_f32,
+    SCALE_FACTORS_INT[0] as f32,
+    SCALE_FACTORS_INT[1] as f32,
+    SCALE_FACTORS_INT[2] as f32,
+    SCALE_FACTORS_INT[3] as f32,
+    SCALE_FACTORS_INT[4] as f32,
+    SCALE_FACTORS_INT[5] as f32,
+    SCALE_FACTORS_INT[6] as f32,
+    SCALE_FACTORS_INT[7] as f32,
+];
+
+/// Writes to undocumented registers, taken from official Arduino library.
+// Fix IC with a fuse bit problem, which lead to a wrong temperature
+// Should not affect ICs without this problem, see ChipInfo::needs_temp_workaround
+pub(crate) const TEMP_WORKAROUND_WRITES: [(u8, u8); 5] = [
+    (0x0E, 0xA5),
+    (0x0F, 0x96),
+    (0x62, 0x02),
+    (0x0E, 0x00),
+    (0x0F, 0x00),
 ];
 
 pub fn calc_busy_time_units(measure_rate: u8, oversampling: u8) -> u32 {
@@ -27,31 +47,125 @@ pub fn calc_total_wait_ms(measure_rate: u8, oversampling: u8) -> u32 {
     calc_busy_time_ms(measure_rate, oversampling) + BUSYTIME_FAILSAFE_MS
 }
 
+pub(crate) fn max_busy_time_exceeded(config: &Config, mode: MeasurementMode) -> bool {
+    let temp_rate = config.temp_rate.unwrap_or_default() as u8;
+    let temp_res = config.temp_res.unwrap_or_default() as u8;
+    let pres_rate = config.pres_rate.unwrap_or_default() as u8;
+    let pres_res = config.pres_res.unwrap_or_default() as u8;
+
+    match mode {
+        MeasurementMode::BackgroundPressure => {
+            calc_busy_time_units(pres_rate, pres_res) >= MAX_BUSYTIME_UNITS
+        }
+        MeasurementMode::BackgroundTemperature => {
+            calc_busy_time_units(temp_rate, temp_res) >= MAX_BUSYTIME_UNITS
+        }
+        MeasurementMode::BackgroundPressureAndTemperature => {
+            calc_busy_time_units(temp_rate, temp_res) + calc_busy_time_units(pres_rate, pres_res)
+                >= MAX_BUSYTIME_UNITS
+        }
+        MeasurementMode::OneShotPressure | MeasurementMode::OneShotTemperature => false,
+    }
+}
+
+/// Wait time for the temperature measurement done during init
+pub(crate) fn init_wait_ms(config: &Config) -> u32 {
+    calc_total_wait_ms(
+        config.temp_rate.unwrap_or_default() as u8,
+        config.temp_res.unwrap_or_default() as u8,
+    )
+}
+
+/// Result bit-shifts (temperature, pressure), required when oversampling rate > 8 times
+pub(crate) fn shift_flags(config: &Config) -> (bool, bool) {
+    let temp_res = config.temp_res.unwrap_or_default() as u8;
+    let pressure_res = config.pres_res.unwrap_or_default() as u8;
+
+    let temp_shift = config.temp_shift || temp_res > TemperatureResolution::_8_SAMPLES as u8;
+    let pres_shift = config.pres_shift || pressure_res > PressureResolution::_8_SAMPLES as u8;
+    (temp_shift, pres_shift)
+}
+
+/// Scale factor kP of a pressure resolution, total over the enum
+pub(crate) const fn pres_scale_factor(res: PressureResolution) -> f32 {
+    match res {
+        PressureResolution::_1_SAMPLES => SCALE_FACTORS[0],
+        PressureResolution::_2_SAMPLES => SCALE_FACTORS[1],
+        PressureResolution::_4_SAMPLES => SCALE_FACTORS[2],
+        PressureResolution::_8_SAMPLES => SCALE_FACTORS[3],
+        PressureResolution::_16_SAMPLES => SCALE_FACTORS[4],
+        PressureResolution::_32_SAMPLES => SCALE_FACTORS[5],
+        PressureResolution::_64_SAMPLES => SCALE_FACTORS[6],
+        PressureResolution::_128_SAMPLES => SCALE_FACTORS[7],
+    }
+}
+
+/// Scale factor kT of a temperature resolution, total over the enum
+pub(crate) const fn temp_scale_factor(res: TemperatureResolution) -> f32 {
+    match res {
+        TemperatureResolution::_1_SAMPLES => SCALE_FACTORS[0],
+        TemperatureResolution::_2_SAMPLES => SCALE_FACTORS[1],
+        TemperatureResolution::_4_SAMPLES => SCALE_FACTORS[2],
+        TemperatureResolution::_8_SAMPLES => SCALE_FACTORS[3],
+        TemperatureResolution::_16_SAMPLES => SCALE_FACTORS[4],
+        TemperatureResolution::_32_SAMPLES => SCALE_FACTORS[5],
+        TemperatureResolution::_64_SAMPLES => SCALE_FACTORS[6],
+        TemperatureResolution::_128_SAMPLES => SCALE_FACTORS[7],
+    }
+}
+
+/// Scale a raw result by the compensation scale factor selected by the
+/// oversampling bits of `cfg` (PRS_CFG or TEMP_CFG), see section 4.9.3.
+pub(crate) fn scale_raw(raw: i32, cfg: u8) -> Option<f32> {
+    let osr = (cfg & 0x07) as usize;
+    SCALE_FACTORS.get(osr).map(|factor| raw as f32 / factor)
+}
+
+/// Fixed-point version of [`scale_raw`], result has [`FIXED_FRAC_BITS`] fraction bits.
+pub(crate) fn scale_raw_fixed(raw: i32, cfg: u8) -> Option<i64> {
+    let osr = (cfg & 0x07) as usize;
+    SCALE_FACTORS_INT
+        .get(osr)
+        .map(|factor| ((raw as i64) << FIXED_FRAC_BITS) / (*factor as i64))
+}
+
+pub(crate) fn i24_from_bytes(bytes: [u8; 3]) -> i32 {
+    let value = ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | (bytes[2] as u32);
+    get_twos_complement(value, 24)
+}
+
 pub(crate) fn prs_cfg_value(current: u8, config: &Config) -> u8 {
-    (current & 0x80)
-        | ((config.pres_rate.unwrap_or_default() as u8) << 4)
-        | (config.pres_res.unwrap_or_default() as u8)
+    PrsCfg {
+        rate: config.pres_rate.unwrap_or_default(),
+        oversampling: config.pres_res.unwrap_or_default(),
+        ..PrsCfg::from_bits(current)
+    }
+    .into_bits()
 }
 
 pub(crate) fn tmp_cfg_value(current: u8, config: &Config, coef_source: Option<bool>) -> u8 {
-    let temp_ext = config.temp_ext.or(coef_source);
-    let ext_bit = match temp_ext {
-        Some(external) => (external as u8) << 7,
-        None => current & 0x80,
-    };
-
-    ext_bit
-        | ((config.temp_rate.unwrap_or_default() as u8) << 4)
-        | (config.temp_res.unwrap_or_default() as u8)
-}
-
-pub(crate) fn cfg_reg_value(config: &Config, temp_shift: bool, pres_shift: bool) -> u8 {
-    ((config.int_hl as u8) << 7)
-        | ((config.int_fifo as u8) << 6)
-        | ((config.int_temp as u8) << 5)
-        | ((config.int_pres as u8) << 4)
-        | ((temp_shift as u8) << 3)
-        | ((pres_shift as u8) << 2)
-        | ((config.fifo_enable as u8) << 1)
-        | (config.spi_mode as u8)
+    let current = TempCfg::from_bits(current);
+    TempCfg {
+        external: config.temp_ext.or(coef_source).unwrap_or(current.external),
+        rate: config.temp_rate.unwrap_or_default(),
+        oversampling: config.temp_res.unwrap_or_default(),
+        ..current
+    }
+    .into_bits()
+}
+
+/// CFG_REG for `config`, with P_SHIFT/T_SHIFT from [`shift_flags`]
+pub(crate) fn cfg_reg_value(config: &Config) -> u8 {
+    let (temp_shift, pres_shift) = shift_flags(config);
+    CfgReg {
+        int_hl: config.int_hl,
+        int_fifo: config.int_fifo,
+        int_temp: config.int_temp,
+        int_pres: config.int_pres,
+        temp_shift,
+        pres_shift,
+        fifo_enable: config.fifo_enable,
+        spi_mode: config.spi_mode,
+    }
+    .into_bits()
 }
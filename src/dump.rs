@@ -0,0 +1,124 @@
+use crate::bitfields::{CfgReg, MeasCfg, PrsCfg, ResetReg, TempCfg, TmpCoefSrce};
+use crate::calibration::{CalibrationCoeffs, COEFFS_LEN};
+use crate::chip::ChipInfo;
+use crate::config::Config;
+use crate::device_internal::{cfg_reg_value, i24_from_bytes, prs_cfg_value, tmp_cfg_value};
+use crate::fifo::FifoStatus;
+use crate::interrupt::InterruptStatus;
+
+/// Length of the PSR_B2..PROD_ID block
+pub(crate) const DUMP_BLOCK_LEN: usize = 14;
+
+/// Decoded contents of every documented register, see [`crate::DPS3xx::register_dump`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisterDump {
+    /// PSR_B2..PSR_B0
+    pub pressure_raw: i32,
+    /// TMP_B2..TMP_B0
+    pub temperature_raw: i32,
+    pub prs_cfg: PrsCfg,
+    pub temp_cfg: TempCfg,
+    pub meas_cfg: MeasCfg,
+    pub cfg_reg: CfgReg,
+    pub int_sts: InterruptStatus,
+    pub fifo_sts: FifoStatus,
+    pub reset: ResetReg,
+    pub chip: ChipInfo,
+    /// COEF_REG_1..18
+    pub coefficients: CalibrationCoeffs,
+    pub tmp_coef_srce: TmpCoefSrce,
+}
+
+/// Bits that differ between a [`RegisterDump`] and the values a [`Config`] programs
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    /// Differing PRS_CFG bits
+    pub prs_cfg: u8,
+    /// Differing TEMP_CFG bits
+    pub temp_cfg: u8,
+    /// Differing CFG_REG bits
+    pub cfg_reg: u8,
+}
+
+impl ConfigDiff {
+    /// True if any bit differs
+    pub fn any(&self) -> bool {
+        self.prs_cfg != 0 || self.temp_cfg != 0 || self.cfg_reg != 0
+    }
+}
+
+impl RegisterDump {
+    pub(crate) fn from_bytes(
+        block: &[u8; DUMP_BLOCK_LEN],
+        coeffs: &[u8; COEFFS_LEN],
+        tmp_coef_srce: u8,
+    ) -> Self {
+        let [p2, p1, p0, t2, t1, t0, prs, tmp, meas, cfg, int, fifo, reset, id] = *block;
+        Self {
+            pressure_raw: i24_from_bytes([p2, p1, p0]),
+            temperature_raw: i24_from_bytes([t2, t1, t0]),
+            prs_cfg: PrsCfg::from_bits(prs),
+            temp_cfg: TempCfg::from_bits(tmp),
+            meas_cfg: MeasCfg::from_bits(meas),
+            cfg_reg: CfgReg::from_bits(cfg),
+            int_sts: InterruptStatus::from_bits(int),
+            fifo_sts: FifoStatus::from_bits(fifo),
+            reset: ResetReg::from_bits(reset),
+            chip: ChipInfo::from_id(id),
+            coefficients: CalibrationCoeffs::decode(coeffs),
+            tmp_coef_srce: TmpCoefSrce::from_bits(tmp_coef_srce),
+        }
+    }
+
+    /// Compare PRS_CFG, TEMP_CFG and CFG_REG with what initialization writes for `config`.
+    ///
+    /// Without [`Config::temp_external`], the expected TMP_EXT is TMP_COEF_SRCE as on init.
+    pub fn diff(&self, config: &Config) -> ConfigDiff {
+        let prs_cfg = self.prs_cfg.into_bits();
+        let temp_cfg = self.temp_cfg.into_bits();
+        let cfg_reg = self.cfg_reg.into_bits();
+        ConfigDiff {
+            prs_cfg: prs_cfg ^ prs_cfg_value(prs_cfg, config),
+            temp_cfg: temp_cfg ^ tmp_cfg_value(temp_cfg, config, Some(self.tmp_coef_srce.external)),
+            cfg_reg: cfg_reg ^ cfg_reg_value(config),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PressureRate, PressureResolution};
+
+    #[test]
+    fn test_from_bytes_and_diff() {
+        let block = [
+            0xFF, 0xFF, 0xFE, 0x00, 0x00, 0x02, 0x26, 0x80, 0xC7, 0x04, 0x01, 0x00, 0x00, 0x10,
+        ];
+        let dump = RegisterDump::from_bytes(&block, &[0; COEFFS_LEN], 0x80);
+        assert_eq!(dump.pressure_raw, -2);
+        assert_eq!(dump.temperature_raw, 2);
+        assert!(dump.int_sts.pres_ready);
+        assert_eq!(dump.chip.revision, 1);
+
+        let mut config = Config::new();
+        config
+            .pres_rate(PressureRate::_4_SPS)
+            .pres_res(PressureResolution::_64_SAMPLES);
+        assert!(!dump.diff(&config).any(), "{:?}", dump.diff(&config));
+
+        config.int_pres(true).temp_external(false);
+        assert_eq!(
+            dump.diff(&config),
+            ConfigDiff {
+                prs_cfg: 0,
+                temp_cfg: 0x80,
+                cfg_reg: 0x10,
+            }
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Adapters for embedded-hal 0.2 peripherals, enabled with the `eh02` feature.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{self, ErrorKind, ErrorType, I2c, Operation, SevenBitAddress};
+use embedded_hal_02::blocking::delay::DelayMs;
+use embedded_hal_02::blocking::i2c::{Write, WriteRead};
+
+/// Error of an [`Eh02I2c`], reported as [`crate::Error::I2CError`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Eh02I2cError<E> {
+    /// Error of the wrapped 0.2 peripheral
+    I2c(E),
+    /// Operation sequence without a 0.2 equivalent, the driver never issues one
+    UnsupportedTransaction,
+}
+
+impl<E> i2c::Error for Eh02I2cError<E>
+where
+    E: core::fmt::Debug,
+{
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// embedded-hal 1.0 [`I2c`] on top of 0.2 `blocking::i2c::Write` and `WriteRead`, to pass to
+/// [`crate::DPS3xx::new`].
+pub struct Eh02I2c<I2C> {
+    i2c: I2C,
+}
+
+impl<I2C> Eh02I2c<I2C> {
+    pub fn new(i2c: I2C) -> Self {
+        Self { i2c }
+    }
+
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C, E> ErrorType for Eh02I2c<I2C>
+where
+    I2C: Write<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    type Error = Eh02I2cError<E>;
+}
+
+impl<I2C, E> I2c for Eh02I2c<I2C>
+where
+    I2C: Write<Error = E> + WriteRead<Error = E>,
+    E: core::fmt::Debug,
+{
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        match operations {
+            [Operation::Write(bytes)] => self.write(address, bytes),
+            [Operation::Write(bytes), Operation::Read(buffer)] => {
+                self.write_read(address, bytes, buffer)
+            }
+            _ => Err(Eh02I2cError::UnsupportedTransaction),
+        }
+    }
+
+    fn write(&mut self, address: SevenBitAddress, bytes: &[u8]) -> Result<(), Self::Error> {
+        Write::write(&mut self.i2c, address, bytes).map_err(Eh02I2cError::I2c)
+    }
+
+    fn write_read(
+        &mut self,
+        address: SevenBitAddress,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        WriteRead::write_read(&mut self.i2c, address, bytes, buffer).map_err(Eh02I2cError::I2c)
+    }
+}
+
+/// embedded-hal 1.0 [`DelayNs`] on top of 0.2 `blocking::delay::DelayMs<u32>`, to pass to
+/// [`crate::DPS3xx::init_and_calibrate`] and the other waiting methods.
+///
+/// Delays shorter than a millisecond are rounded up to one.
+pub struct Eh02Delay<D> {
+    delay: D,
+}
+
+impl<D> Eh02Delay<D> {
+    pub fn new(delay: D) -> Self {
+        Self { delay }
+    }
+
+    pub fn release(self) -> D {
+        self.delay
+    }
+}
+
+impl<D> DelayNs for Eh02Delay<D>
+where
+    D: DelayMs<u32>,
+{
+    fn delay_ns(&mut self, ns: u32) {
+        self.delay.delay_ms(ns.div_ceil(1_000_000));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.delay.delay_ms(us.div_ceil(1_000));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay.delay_ms(ms);
+    }
+}
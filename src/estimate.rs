@@ -0,0 +1,158 @@
+use crate::config::{Config, PressureResolution};
+use crate::device::MeasurementMode;
+use crate::device_internal::{calc_busy_time_units, BUSYTIME_SCALING};
+
+/// Standby supply current in µA
+const STANDBY_CURRENT_UA: f32 = 0.5;
+/// Supply current while measuring pressure in µA.
+/// Derived from the datasheet's 1.7 µA for one single-sample pressure measurement per second.
+const PRESSURE_ACTIVE_CURRENT_UA: f32 = 335.0;
+/// Supply current while measuring temperature in µA.
+/// Derived from the datasheet's 1.5 µA for one single-sample temperature measurement per second.
+const TEMPERATURE_ACTIVE_CURRENT_UA: f32 = 280.0;
+
+/// Expected power budget and noise of a [`Config`], see [`Config::estimate`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PowerEstimate {
+    /// Average supply current in µA
+    pub current_ua: f32,
+    /// RMS noise of one pressure sample in Pa
+    pub pressure_noise_pa: f32,
+    /// Pressure results per second
+    pub pressure_rate_hz: f32,
+    /// Temperature results per second
+    pub temperature_rate_hz: f32,
+}
+
+impl Config {
+    /// Estimate current, pressure noise and output rate for `mode`.
+    ///
+    /// Uses the same timing model as [`crate::calc_busy_time_units`]. One-shot modes are
+    /// estimated as one measurement per second, see [`Self::estimate_duty_cycled`] for other
+    /// intervals.
+    pub fn estimate(&self, mode: MeasurementMode) -> PowerEstimate {
+        let pres_rate = self.pres_rate.unwrap_or_default() as u8;
+        let temp_rate = self.temp_rate.unwrap_or_default() as u8;
+        let (pres_hz, temp_hz) = match mode {
+            MeasurementMode::OneShotPressure => (1.0, 0.0),
+            MeasurementMode::OneShotTemperature => (0.0, 1.0),
+            MeasurementMode::BackgroundPressure => (rate_hz(pres_rate), 0.0),
+            MeasurementMode::BackgroundTemperature => (0.0, rate_hz(temp_rate)),
+            MeasurementMode::BackgroundPressureAndTemperature => {
+                (rate_hz(pres_rate), rate_hz(temp_rate))
+            }
+        };
+        self.estimate_for_rates(pres_hz, temp_hz)
+    }
+
+    /// Estimate for one pressure and one temperature one-shot measurement every `interval_ms`.
+    pub fn estimate_duty_cycled(&self, interval_ms: u32) -> PowerEstimate {
+        let hz = 1000.0 / interval_ms.max(1) as f32;
+        self.estimate_for_rates(hz, hz)
+    }
+
+    fn estimate_for_rates(&self, pres_hz: f32, temp_hz: f32) -> PowerEstimate {
+        let pres_res = self.pres_res.unwrap_or_default();
+        let temp_res = self.temp_res.unwrap_or_default() as u8;
+        let pres_busy = pres_hz * measurement_time_s(pres_res as u8);
+        let temp_busy = temp_hz * measurement_time_s(temp_res);
+
+        PowerEstimate {
+            current_ua: STANDBY_CURRENT_UA
+                + pres_busy * PRESSURE_ACTIVE_CURRENT_UA
+                + temp_busy * TEMPERATURE_ACTIVE_CURRENT_UA,
+            pressure_noise_pa: pressure_noise_pa(pres_res),
+            pressure_rate_hz: pres_hz,
+            temperature_rate_hz: temp_hz,
+        }
+    }
+}
+
+/// Pressure precision (Pa RMS) per oversampling setting, see datasheet section 8.3
+fn pressure_noise_pa(res: PressureResolution) -> f32 {
+    match res {
+        PressureResolution::_1_SAMPLES => 2.5,
+        PressureResolution::_2_SAMPLES => 1.0,
+        PressureResolution::_4_SAMPLES => 0.5,
+        PressureResolution::_8_SAMPLES => 0.4,
+        PressureResolution::_16_SAMPLES => 0.35,
+        PressureResolution::_32_SAMPLES => 0.3,
+        PressureResolution::_64_SAMPLES | PressureResolution::_128_SAMPLES => 0.2,
+    }
+}
+
+fn rate_hz(rate: u8) -> f32 {
+    (1u32 << rate) as f32
+}
+
+/// Duration of a single measurement, busy time at 1 measurement per second
+fn measurement_time_s(oversampling: u8) -> f32 {
+    calc_busy_time_units(0, oversampling) as f32 / (BUSYTIME_SCALING * 1000) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PressureRate;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= expected * 0.05,
+            "{actual} not within 5% of {expected}"
+        );
+    }
+
+    #[test]
+    fn test_datasheet_current_single_measurements() {
+        let cfg = Config::new();
+        assert_close(
+            cfg.estimate(MeasurementMode::OneShotPressure).current_ua,
+            1.7,
+        );
+        assert_close(
+            cfg.estimate(MeasurementMode::OneShotTemperature).current_ua,
+            1.5,
+        );
+    }
+
+    #[test]
+    fn test_background_estimate() {
+        let mut cfg = Config::new();
+        cfg.pres_rate(PressureRate::_8_SPS)
+            .pres_res(PressureResolution::_64_SAMPLES);
+        let estimate = cfg.estimate(MeasurementMode::BackgroundPressure);
+        assert_eq!(estimate.pressure_rate_hz, 8.0);
+        assert_eq!(estimate.temperature_rate_hz, 0.0);
+        assert_eq!(estimate.pressure_noise_pa, 0.2);
+        // 8 * 104.4 ms busy
+        assert_close(estimate.current_ua, 0.5 + 0.8352 * 335.0);
+    }
+
+    #[test]
+    fn test_preset_budgets() {
+        let mode = MeasurementMode::BackgroundPressureAndTemperature;
+        for (cfg, current_ua) in [
+            (Config::low_power(), 2.7),
+            (Config::weather_station(), 3.25),
+            (Config::indoor_navigation(), 20.0),
+            (Config::sports(), 41.5),
+            (Config::high_rate_flight(), 196.7),
+        ] {
+            assert_close(cfg.estimate(mode).current_ua, current_ua);
+        }
+    }
+
+    #[test]
+    fn test_duty_cycled_estimate() {
+        let cfg = Config::low_power();
+        let estimate = cfg.estimate_duty_cycled(10_000);
+        assert_eq!(estimate.pressure_rate_hz, 0.1);
+        assert_eq!(estimate.pressure_noise_pa, 2.5);
+        assert!(
+            estimate.current_ua < cfg.estimate_duty_cycled(1_000).current_ua,
+            "longer interval should draw less"
+        );
+    }
+}
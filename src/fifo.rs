@@ -0,0 +1,91 @@
+/// Number of results the FIFO can hold
+pub const FIFO_SIZE: usize = 32;
+
+/// Raw value read from the result registers when the FIFO is empty.
+pub(crate) const FIFO_EMPTY_MARKER: i32 = -0x80_0000;
+
+/// FIFO_STS register contents
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FifoStatus {
+    pub full: bool,
+    pub empty: bool,
+}
+
+impl FifoStatus {
+    pub const fn from_bits(status: u8) -> Self {
+        Self {
+            full: (status & (1 << 1)) != 0,
+            empty: (status & 1) != 0,
+        }
+    }
+
+    pub const fn into_bits(self) -> u8 {
+        ((self.full as u8) << 1) | self.empty as u8
+    }
+}
+
+/// A single raw FIFO entry, told apart by the LSB of the 24-bit result.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FifoEntry {
+    Pressure(i32),
+    Temperature(i32),
+    /// FIFO has no more results
+    Empty,
+}
+
+impl FifoEntry {
+    /// Decode a sign-extended 24-bit result read from the FIFO
+    pub fn from_raw(raw: i32) -> Self {
+        if raw == FIFO_EMPTY_MARKER {
+            Self::Empty
+        } else if raw & 1 == 1 {
+            Self::Pressure(raw)
+        } else {
+            Self::Temperature(raw)
+        }
+    }
+}
+
+/// Compensated FIFO result, pressure in Pa and temperature in degrees Celsius.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FifoSample {
+    Pressure(f32),
+    Temperature(f32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fifo_entry_from_raw() {
+        assert_eq!(FifoEntry::from_raw(0x1235), FifoEntry::Pressure(0x1235));
+        assert_eq!(FifoEntry::from_raw(-3), FifoEntry::Pressure(-3));
+        assert_eq!(FifoEntry::from_raw(0x1234), FifoEntry::Temperature(0x1234));
+        assert_eq!(FifoEntry::from_raw(-0x80_0000), FifoEntry::Empty);
+    }
+
+    #[test]
+    fn test_fifo_status_from_bits() {
+        assert_eq!(
+            FifoStatus::from_bits(0x01),
+            FifoStatus {
+                full: false,
+                empty: true
+            }
+        );
+        assert_eq!(
+            FifoStatus::from_bits(0x02),
+            FifoStatus {
+                full: true,
+                empty: false
+            }
+        );
+    }
+}
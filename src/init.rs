@@ -0,0 +1,165 @@
+//! Bus independent parts of initialization, shared by the blocking and async drivers and
+//! [`crate::DPS3xxManager`]. The drivers only perform the bus transfers and waits.
+
+use crate::calibration::{CalibrationCoeffs, COEFFS_LEN};
+use crate::chip::{ChipInfo, ChipVariant};
+use crate::config::Config;
+use crate::device::{Error, InitStage, Status};
+
+/// Poll interval while waiting for COEF_RDY
+pub(crate) const COEF_POLL_MS: u32 = 10;
+
+/// Progress of `poll_init`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct InitState {
+    /// Init temperature measurement done, `finish_init` may proceed
+    pub(crate) ready: bool,
+    /// Init temperature measurement started and not yet read
+    pub(crate) temp_started: bool,
+}
+
+/// What `poll_init` does after reading MEAS_CFG
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum InitStep {
+    /// Nothing to do yet, wait [`crate::device_internal::init_wait_ms`]
+    Wait,
+    /// Start the one-shot temperature measurement, then wait
+    StartTemperature,
+    /// Read and discard the temperature result and go to standby
+    FinishTemperature,
+}
+
+impl InitState {
+    pub(crate) fn step(&self, status: Status) -> InitStep {
+        if !status.init_complete {
+            InitStep::Wait
+        } else if !self.temp_started {
+            InitStep::StartTemperature
+        } else if !status.temp_ready {
+            InitStep::Wait
+        } else {
+            InitStep::FinishTemperature
+        }
+    }
+
+    /// Stage reported by [`Error::InitTimeout`] while this state is pending
+    pub(crate) fn stage(&self) -> InitStage {
+        if self.temp_started {
+            InitStage::WaitingInitTempReady
+        } else {
+            InitStage::WaitingInitComplete
+        }
+    }
+}
+
+/// Remaining [`Config::init_timeout_ms`] of one sensor
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct InitBudget {
+    remaining_ms: u32,
+}
+
+impl InitBudget {
+    pub(crate) fn new(config: &Config) -> Self {
+        Self {
+            remaining_ms: config.init_timeout_ms,
+        }
+    }
+
+    /// [`Error::InitTimeout`] if waiting `wait_ms` would exceed the timeout
+    pub(crate) fn check<E>(&self, wait_ms: u32, stage: InitStage) -> Result<(), Error<E>> {
+        if wait_ms > self.remaining_ms {
+            return Err(Error::InitTimeout(stage));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn spend(&mut self, wait_ms: u32) {
+        self.remaining_ms = self.remaining_ms.saturating_sub(wait_ms);
+    }
+
+    /// [`Self::check`] and [`Self::spend`], before waiting `wait_ms`
+    pub(crate) fn take<E>(&mut self, wait_ms: u32, stage: InitStage) -> Result<(), Error<E>> {
+        self.check(wait_ms, stage)?;
+        self.spend(wait_ms);
+        Ok(())
+    }
+}
+
+/// [`Error::InvalidProductId`] unless PROD_ID is a supported DPS3xx
+pub(crate) fn check_chip<E>(chip: ChipInfo) -> Result<(), Error<E>> {
+    if chip.variant == ChipVariant::Unknown {
+        return Err(Error::InvalidProductId);
+    }
+    Ok(())
+}
+
+/// Whether `start_init` writes [`crate::device_internal::TEMP_WORKAROUND_WRITES`]
+pub(crate) fn apply_temp_workaround(config: &Config, chip: ChipInfo) -> bool {
+    config
+        .temp_workaround
+        .unwrap_or(chip.needs_temp_workaround())
+}
+
+/// Decode a coefficient block, `again` is the second read with [`Config::coeff_double_read`]
+pub(crate) fn check_coefficients<E>(
+    bytes: &[u8; COEFFS_LEN],
+    again: Option<&[u8; COEFFS_LEN]>,
+) -> Result<CalibrationCoeffs, Error<E>> {
+    if again.is_some_and(|again| again != bytes) {
+        return Err(Error::InvalidCoefficients);
+    }
+    CalibrationCoeffs::checked_decode(bytes).ok_or(Error::InvalidCoefficients)
+}
+
+/// Whether to re-read after `err`, counting down `retries` ([`Config::coeff_retries`])
+pub(crate) fn coefficient_retry<E>(retries: &mut u8, err: &Error<E>) -> bool {
+    if matches!(err, Error::InvalidCoefficients) && *retries > 0 {
+        *retries -= 1;
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(init_complete: bool, temp_ready: bool) -> Status {
+        Status {
+            coef_ready: false,
+            init_complete,
+            temp_ready,
+            pres_ready: false,
+        }
+    }
+
+    #[test]
+    fn test_init_steps() {
+        let mut state = InitState::default();
+        assert_eq!(state.step(status(false, false)), InitStep::Wait);
+        assert_eq!(state.stage(), InitStage::WaitingInitComplete);
+        assert_eq!(state.step(status(true, false)), InitStep::StartTemperature);
+
+        state.temp_started = true;
+        assert_eq!(state.step(status(true, false)), InitStep::Wait);
+        assert_eq!(state.stage(), InitStage::WaitingInitTempReady);
+        assert_eq!(state.step(status(true, true)), InitStep::FinishTemperature);
+    }
+
+    #[test]
+    fn test_init_budget() {
+        let mut config = Config::new();
+        config.init_timeout_ms(25);
+        let mut budget = InitBudget::new(&config);
+
+        assert!(budget
+            .take::<()>(20, InitStage::WaitingInitComplete)
+            .is_ok());
+        assert!(matches!(
+            budget.take::<()>(10, InitStage::WaitingCoefReady),
+            Err(Error::InitTimeout(InitStage::WaitingCoefReady))
+        ));
+        assert!(budget.take::<()>(5, InitStage::WaitingCoefReady).is_ok());
+    }
+}
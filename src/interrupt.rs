@@ -0,0 +1,64 @@
+/// INT_STS register contents, the register is cleared when read.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterruptStatus {
+    pub fifo_full: bool,
+    pub temp_ready: bool,
+    pub pres_ready: bool,
+}
+
+impl InterruptStatus {
+    pub const fn from_bits(status: u8) -> Self {
+        Self {
+            fifo_full: (status & (1 << 2)) != 0,
+            temp_ready: (status & (1 << 1)) != 0,
+            pres_ready: (status & 1) != 0,
+        }
+    }
+
+    pub const fn into_bits(self) -> u8 {
+        ((self.fifo_full as u8) << 2) | ((self.temp_ready as u8) << 1) | self.pres_ready as u8
+    }
+
+    /// True if any interrupt source is set
+    pub fn any(&self) -> bool {
+        self.fifo_full || self.temp_ready || self.pres_ready
+    }
+}
+
+/// Result of an interrupt: the cleared status plus the compensated values it signalled.
+///
+/// A full FIFO is only reported in `status`, drain it with `read_fifo`.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InterruptSample {
+    pub status: InterruptStatus,
+    /// Pressure in Pa, if `status.pres_ready`
+    pub pressure: Option<f32>,
+    /// Temperature in degrees Celsius, if `status.temp_ready`
+    pub temperature: Option<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interrupt_status_from_bits() {
+        let status = InterruptStatus::from_bits(0x07);
+        assert!(status.fifo_full && status.temp_ready && status.pres_ready);
+
+        let status = InterruptStatus::from_bits(0x01);
+        assert_eq!(
+            status,
+            InterruptStatus {
+                fifo_full: false,
+                temp_ready: false,
+                pres_ready: true
+            }
+        );
+        assert!(!InterruptStatus::from_bits(0xF8).any());
+    }
+}
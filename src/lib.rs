@@ -1,27 +1,99 @@
 //!
-//! DPS3xx embedded-hal I2C driver crate
+//! DPS3xx embedded-hal I2C/SPI driver crate
 //!
 //! A platform agnostic driver to interface with the dps3xx barometric pressure & temp sensor.
-//! This driver uses I2C via [embedded-hal]. Note that the dps3xx also supports SPI, however that
-//! is not (yet) implemented in this driver.
+//! This driver uses I2C or SPI (4-wire and 3-wire) via [embedded-hal].
+//!
+//! With the `async` feature, [`DPS3xxAsync`] provides a subset of the API (init, status,
+//! register and basic measurement methods) on top of [embedded-hal-async] I2C and delay
+//! traits. The README's Async section lists the supported methods.
+//!
+//! With the `serde` feature, configuration, measurement and status types implement
+//! `Serialize`/`Deserialize`.
+//!
+//! Barometric altitude helpers ([`altitude`], [`sea_level_pressure`], [`height_above_qfe`],
+//! [`pressure_altitude`]) are included and work without `std` or `libm`.
+//!
+//! Typed register contents ([`PrsCfg`], [`TempCfg`], [`MeasCfg`], [`CfgReg`],
+//! [`InterruptStatus`], [`FifoStatus`], [`ResetReg`], [`TmpCoefSrce`]) can be read and written
+//! with [`DPS3xx::read_register`], [`DPS3xx::write_register`] and [`DPS3xx::modify_register`].
+//!
+//! With the `eh02` feature, [`Eh02I2c`] and [`Eh02Delay`] adapt embedded-hal 0.2 blocking I2C
+//! and delay implementations to the traits this driver takes.
+//!
+//! [`RawDecoder`] converts logged raw register bytes without a sensor, the `std` feature adds
+//! bulk decoding into a `Vec`.
 //!
 //! [embedded-hal]: https://docs.rs/embedded-hal
+//! [embedded-hal-async]: https://docs.rs/embedded-hal-async
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod altitude;
+mod bitfields;
 mod bus;
 mod calibration;
+mod chip;
+mod clock;
 mod config;
+mod decoder;
 mod device;
+#[cfg(feature = "async")]
+mod device_async;
 mod device_internal;
+mod dump;
+#[cfg(feature = "eh02")]
+mod eh02;
+mod estimate;
+mod fifo;
+mod init;
+mod interrupt;
+mod manager;
 mod register;
+mod retry;
+mod self_test;
+#[cfg(feature = "serde")]
+mod serde_support;
 
+pub use altitude::{
+    altitude, altitude_temperature_corrected, height_above_qfe, pressure_altitude,
+    sea_level_pressure, STANDARD_PRESSURE_PA,
+};
+pub use bitfields::{
+    CfgReg, MeasCfg, PrsCfg, ReadableRegister, RegisterBits, ResetReg, TempCfg, TmpCoefSrce,
+    WritableRegister,
+};
+#[cfg(feature = "async")]
+pub use bus::AsyncBus;
+pub use bus::{Bus, I2cBus, SpiBus};
+pub use calibration::{
+    CalibrationBlobError, CalibrationCoeffs, CALIBRATION_BLOB_LEN, COEFFS_LEN,
+    PRESSURE_FIXED_SCALE, TEMPERATURE_FIXED_SCALE,
+};
+pub use chip::{ChipInfo, ChipVariant};
+pub use clock::{Clock, Timestamped};
 pub use config::{
-    Config, PressureRate, PressureResolution, TemperatureRate, TemperatureResolution,
+    Config, ConfigError, PressureRate, PressureResolution, TemperatureRate, TemperatureResolution,
 };
+pub use decoder::{RawDecoder, RAW_BURST_LEN};
 pub use device::{
     calc_busy_time_ms, calc_busy_time_units, calc_total_wait_ms, Calibrated, Configured, DPS3xx,
-    Error, InitInProgress, InitPoll, InitStage, IsConfigured, MeasurementMode, Status,
-    Unconfigured, BUSYTIME_FAILSAFE_MS, BUSYTIME_SCALING, MAX_BUSYTIME_UNITS,
+    DPS3xxI2c, DPS3xxSpi, Error, InitInProgress, InitPoll, InitStage, IsConfigured, Measurement,
+    MeasurementMode, Status, Unconfigured, BUSYTIME_FAILSAFE_MS, BUSYTIME_SCALING,
+    MAX_BUSYTIME_UNITS,
 };
+#[cfg(feature = "async")]
+pub use device_async::{DPS3xxAsync, DPS3xxAsyncI2c};
+pub use dump::{ConfigDiff, RegisterDump};
+#[cfg(feature = "eh02")]
+pub use eh02::{Eh02Delay, Eh02I2c, Eh02I2cError};
+pub use estimate::PowerEstimate;
+pub use fifo::{FifoEntry, FifoSample, FifoStatus, FIFO_SIZE};
+pub use interrupt::{InterruptSample, InterruptStatus};
+pub use manager::{DPS3xxManager, ManagedSample};
 pub use register::Register;
+pub use retry::{RetryBus, RetryConfig, RetryPolicy, RetryStats};
+pub use self_test::SelfTestReport;
@@ -0,0 +1,257 @@
+use crate::bus::Bus;
+use crate::clock::Clock;
+use crate::device::{
+    Calibrated, Configured, DPS3xx, Error, InitInProgress, InitPoll, InitStage, Measurement,
+    MeasurementMode, Unconfigured,
+};
+use crate::init::{InitBudget, COEF_POLL_MS};
+use core::array;
+use core::mem;
+use embedded_hal::delay::DelayNs;
+
+enum Slot<B> {
+    Unconfigured(DPS3xx<B, Unconfigured>),
+    Initializing(DPS3xx<B, InitInProgress>),
+    Configured(DPS3xx<B, Configured>),
+    Ready(DPS3xx<B, Calibrated>),
+    /// Init failed, the driver is kept for [`DPS3xxManager::release`]
+    Failed(DPS3xx<B, Unconfigured>),
+    /// Only while a driver moves between states
+    Moving,
+}
+
+impl<B> Slot<B>
+where
+    B: Bus,
+{
+    fn into_unconfigured(self) -> Option<DPS3xx<B, Unconfigured>> {
+        match self {
+            Slot::Unconfigured(dps) | Slot::Failed(dps) => Some(dps),
+            Slot::Initializing(dps) => Some(dps.into_unconfigured()),
+            Slot::Configured(dps) => Some(dps.into_unconfigured()),
+            Slot::Ready(dps) => Some(dps.into_unconfigured()),
+            Slot::Moving => None,
+        }
+    }
+
+    /// Mark as failed, keeping the driver
+    fn fail(&mut self) {
+        if let Some(dps) = mem::replace(self, Slot::Moving).into_unconfigured() {
+            *self = Slot::Failed(dps);
+        }
+    }
+}
+
+/// Sample from one sensor of a [`DPS3xxManager`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct ManagedSample<E> {
+    /// Time from the [`Clock`] passed to [`DPS3xxManager::read_all`], in µs. Backdated to the
+    /// conversion as in [`DPS3xx::read_sample_timestamped`], or the time the sensor was skipped.
+    pub timestamp_us: u64,
+    pub measurement: Result<Measurement, Error<E>>,
+}
+
+/// Drives `N` sensors of the same bus type together.
+///
+/// Sensors on one bus (e.g. 0x76 and 0x77) or on several buses share a bus type through
+/// a bus sharing wrapper such as `embedded-hal-bus`.
+/// A sensor that fails to initialize is reported and skipped, the others keep working.
+pub struct DPS3xxManager<B, const N: usize> {
+    slots: [Slot<B>; N],
+}
+
+impl<B, E, const N: usize> DPS3xxManager<B, N>
+where
+    B: Bus<Error = E>,
+{
+    pub fn new(sensors: [DPS3xx<B, Unconfigured>; N]) -> Self {
+        Self {
+            slots: sensors.map(Slot::Unconfigured),
+        }
+    }
+
+    /// Initialize and calibrate all sensors, interleaving their `poll_init` waits.
+    ///
+    /// Each sensor keeps its own `Config::init_timeout_ms`. Returns the init result per sensor.
+    /// On repeated calls, calibrated sensors report `Ok` and failed ones
+    /// [`Error::NotInitialized`].
+    pub fn init_all<D>(&mut self, delay: &mut D) -> [Result<(), Error<E>>; N]
+    where
+        D: DelayNs,
+    {
+        let mut results: [Result<(), Error<E>>; N] = array::from_fn(|_| Ok(()));
+        let mut budgets: [InitBudget; N] = array::from_fn(|_| InitBudget::default());
+
+        for ((slot, result), budget) in self
+            .slots
+            .iter_mut()
+            .zip(results.iter_mut())
+            .zip(budgets.iter_mut())
+        {
+            *slot = match mem::replace(slot, Slot::Moving) {
+                Slot::Unconfigured(dps) => {
+                    *budget = dps.init_budget();
+                    match dps.try_start_init() {
+                        Ok(dps) => Slot::Initializing(dps),
+                        Err((dps, err)) => {
+                            *result = Err(err);
+                            Slot::Failed(dps)
+                        }
+                    }
+                }
+                Slot::Failed(dps) => {
+                    *result = Err(Error::NotInitialized);
+                    Slot::Failed(dps)
+                }
+                other => other,
+            };
+        }
+
+        loop {
+            let mut wait_ms: Option<u32> = None;
+            for ((slot, result), budget) in self
+                .slots
+                .iter_mut()
+                .zip(results.iter_mut())
+                .zip(budgets.iter())
+            {
+                match Self::advance(slot) {
+                    Ok(None) => {}
+                    Ok(Some((pending_ms, stage))) => match budget.check(pending_ms, stage) {
+                        Ok(()) => {
+                            wait_ms = Some(wait_ms.map_or(pending_ms, |ms| ms.min(pending_ms)));
+                        }
+                        Err(err) => {
+                            slot.fail();
+                            *result = Err(err);
+                        }
+                    },
+                    Err(err) => {
+                        slot.fail();
+                        *result = Err(err);
+                    }
+                }
+            }
+
+            let Some(wait_ms) = wait_ms else {
+                break;
+            };
+            delay.delay_ms(wait_ms);
+            for (slot, budget) in self.slots.iter().zip(budgets.iter_mut()) {
+                if matches!(slot, Slot::Initializing(_) | Slot::Configured(_)) {
+                    budget.spend(wait_ms);
+                }
+            }
+        }
+
+        results
+    }
+
+    /// Move a sensor as far through initialization as possible without waiting.
+    ///
+    /// Returns how long it wants to wait, or `None` once it is calibrated (or failed). On error
+    /// the driver is left in the slot for the caller to [`Slot::fail`].
+    fn advance(slot: &mut Slot<B>) -> Result<Option<(u32, InitStage)>, Error<E>> {
+        loop {
+            match mem::replace(slot, Slot::Moving) {
+                Slot::Initializing(mut dps) => {
+                    let poll = dps.poll_init();
+                    match poll {
+                        Ok(InitPoll::Pending(wait_ms)) => {
+                            let stage = dps.init_stage();
+                            *slot = Slot::Initializing(dps);
+                            return Ok(Some((wait_ms, stage)));
+                        }
+                        Ok(InitPoll::Ready) => match dps.finish_init() {
+                            Ok(dps) => *slot = Slot::Configured(dps),
+                            Err(dps) => *slot = Slot::Initializing(dps),
+                        },
+                        Err(err) => {
+                            *slot = Slot::Initializing(dps);
+                            return Err(err);
+                        }
+                    }
+                }
+                Slot::Configured(mut dps) => {
+                    match dps.coef_ready() {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            *slot = Slot::Configured(dps);
+                            return Ok(Some((COEF_POLL_MS, InitStage::WaitingCoefReady)));
+                        }
+                        Err(err) => {
+                            *slot = Slot::Configured(dps);
+                            return Err(err);
+                        }
+                    }
+                    return match dps.try_read_calibration_coefficients() {
+                        Ok(dps) => {
+                            *slot = Slot::Ready(dps);
+                            Ok(None)
+                        }
+                        Err((dps, err)) => {
+                            *slot = Slot::Configured(dps);
+                            Err(err)
+                        }
+                    };
+                }
+                other => {
+                    *slot = other;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    /// Start the same measurement mode on all calibrated sensors
+    pub fn start_all(&mut self, mode: MeasurementMode) -> [Result<(), Error<E>>; N] {
+        self.slots.each_mut().map(|slot| match slot {
+            Slot::Ready(dps) => dps.start_measurement(mode),
+            _ => Err(Error::NotInitialized),
+        })
+    }
+
+    /// Read pressure and temperature from all sensors with
+    /// [`DPS3xx::read_sample_timestamped`], one after the other.
+    ///
+    /// Each sample is timestamped on its own from `clock`, as the sensors do not convert in
+    /// lockstep even when started together.
+    pub fn read_all<C>(&mut self, clock: &mut C) -> [ManagedSample<E>; N]
+    where
+        C: Clock,
+    {
+        self.slots.each_mut().map(|slot| {
+            let sample = match slot {
+                Slot::Ready(dps) => dps.read_sample_timestamped(clock),
+                _ => Err(Error::NotInitialized),
+            };
+            match sample {
+                Ok(sample) => ManagedSample {
+                    timestamp_us: sample.timestamp_us,
+                    measurement: Ok(sample.value),
+                },
+                Err(err) => ManagedSample {
+                    timestamp_us: clock.now_us(),
+                    measurement: Err(err),
+                },
+            }
+        })
+    }
+
+    /// Calibrated sensor at `index`, `None` if it is not initialized
+    pub fn sensor_mut(&mut self, index: usize) -> Option<&mut DPS3xx<B, Calibrated>> {
+        match self.slots.get_mut(index) {
+            Some(Slot::Ready(dps)) => Some(dps),
+            _ => None,
+        }
+    }
+
+    /// Hand back every driver, including the ones that failed to initialize.
+    ///
+    /// Drivers are returned as [`Unconfigured`] with their config, to [`DPS3xx::release`] the
+    /// bus or to retry [`DPS3xx::start_init`]. The sensors are left as they are.
+    pub fn release(self) -> [Option<DPS3xx<B, Unconfigured>>; N] {
+        self.slots.map(Slot::into_unconfigured)
+    }
+}
@@ -0,0 +1,295 @@
+use crate::bus::{Bus, Sealed};
+use crate::register::Register;
+use embedded_hal::delay::DelayNs;
+
+/// How often one class of bus transactions is retried
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts after the first failed one
+    pub max_retries: u8,
+    /// Wait before each retry in µs
+    pub delay_us: u32,
+}
+
+impl RetryPolicy {
+    /// Fail on the first error
+    pub const NONE: Self = Self::new(0, 0);
+
+    pub const fn new(max_retries: u8, delay_us: u32) -> Self {
+        Self {
+            max_retries,
+            delay_us,
+        }
+    }
+}
+
+/// Retry policies per operation class, see [`crate::DPS3xx::with_retry`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetryConfig {
+    pub(crate) config_writes: RetryPolicy,
+    pub(crate) status_polls: RetryPolicy,
+    pub(crate) data_reads: RetryPolicy,
+}
+
+impl RetryConfig {
+    /// No retries for any operation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Same policy for every operation
+    pub fn all(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.config_writes = policy;
+        self.status_polls = policy;
+        self.data_reads = policy;
+        self
+    }
+
+    /// Register writes: configuration, measurement control, reset and FIFO flush
+    pub fn config_writes(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.config_writes = policy;
+        self
+    }
+
+    /// Reads of MEAS_CFG, INT_STS and FIFO_STS.
+    ///
+    /// INT_STS is cleared by a read, a retried read can miss flags of a lost response.
+    pub fn status_polls(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.status_polls = policy;
+        self
+    }
+
+    /// All other reads: results, coefficients, configuration and product ID.
+    ///
+    /// With the FIFO enabled every result read pops entries, a retried read that failed
+    /// partway loses the samples it already popped. Use [`RetryPolicy::NONE`] if every
+    /// sample counts.
+    pub fn data_reads(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.data_reads = policy;
+        self
+    }
+}
+
+/// Counters of a [`RetryBus`], saturating at `u32::MAX`
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetryStats {
+    /// Failed attempts that were retried
+    pub retries: u32,
+    /// Transactions that still failed after all retries and were reported as an error
+    pub failures: u32,
+}
+
+/// Transport wrapper that retries failed transactions of the wrapped bus.
+///
+/// Created by [`crate::DPS3xx::with_retry`].
+pub struct RetryBus<B, D> {
+    bus: B,
+    delay: D,
+    config: RetryConfig,
+    stats: RetryStats,
+}
+
+impl<B, D> RetryBus<B, D> {
+    pub(crate) fn new(bus: B, delay: D, config: &RetryConfig) -> Self {
+        Self {
+            bus,
+            delay,
+            config: *config,
+            stats: RetryStats::default(),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> RetryStats {
+        self.stats
+    }
+
+    pub(crate) fn reset_stats(&mut self) {
+        self.stats = RetryStats::default();
+    }
+
+    pub(crate) fn release(self) -> (B, D) {
+        (self.bus, self.delay)
+    }
+
+    fn read_policy(&self, reg: Register) -> RetryPolicy {
+        match reg {
+            Register::MEAS_CFG | Register::INT_STS | Register::FIFO_STS => self.config.status_polls,
+            _ => self.config.data_reads,
+        }
+    }
+}
+
+impl<B, D> RetryBus<B, D>
+where
+    B: Bus,
+    D: DelayNs,
+{
+    fn retry<T, F>(&mut self, policy: RetryPolicy, mut op: F) -> Result<T, B::Error>
+    where
+        F: FnMut(&mut B) -> Result<T, B::Error>,
+    {
+        let mut retries = 0;
+        loop {
+            match op(&mut self.bus) {
+                Ok(value) => return Ok(value),
+                Err(_) if retries < policy.max_retries => {
+                    retries += 1;
+                    self.stats.retries = self.stats.retries.saturating_add(1);
+                    self.delay.delay_us(policy.delay_us);
+                }
+                Err(err) => {
+                    self.stats.failures = self.stats.failures.saturating_add(1);
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+impl<B, D> Sealed for RetryBus<B, D> {}
+
+impl<B, D> Bus for RetryBus<B, D>
+where
+    B: Bus,
+    D: DelayNs,
+{
+    type Error = B::Error;
+
+    const SPI: bool = B::SPI;
+
+    fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), Self::Error> {
+        let policy = self.config.config_writes;
+        self.retry(policy, |bus| bus.write_reg(reg, value))
+    }
+
+    fn write_addr(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {
+        let policy = self.config.config_writes;
+        self.retry(policy, |bus| bus.write_addr(addr, value))
+    }
+
+    fn read_reg(&mut self, reg: Register) -> Result<u8, Self::Error> {
+        let policy = self.read_policy(reg);
+        self.retry(policy, |bus| bus.read_reg(reg))
+    }
+
+    fn read_many(&mut self, start: Register, buf: &mut [u8]) -> Result<(), Self::Error> {
+        let policy = self.read_policy(start);
+        self.retry(policy, |bus| bus.read_many(start, buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fails the first `failures` transactions
+    struct FlakyBus {
+        failures: u32,
+        attempts: u32,
+    }
+
+    impl Sealed for FlakyBus {}
+
+    impl Bus for FlakyBus {
+        type Error = ();
+
+        const SPI: bool = false;
+
+        fn write_reg(&mut self, _reg: Register, _value: u8) -> Result<(), ()> {
+            self.attempt().map(|_| ())
+        }
+
+        fn write_addr(&mut self, _addr: u8, _value: u8) -> Result<(), ()> {
+            self.attempt().map(|_| ())
+        }
+
+        fn read_reg(&mut self, _reg: Register) -> Result<u8, ()> {
+            self.attempt()
+        }
+
+        fn read_many(&mut self, _start: Register, buf: &mut [u8]) -> Result<(), ()> {
+            let value = self.attempt()?;
+            buf.fill(value);
+            Ok(())
+        }
+    }
+
+    impl FlakyBus {
+        fn attempt(&mut self) -> Result<u8, ()> {
+            self.attempts += 1;
+            if self.attempts <= self.failures {
+                Err(())
+            } else {
+                Ok(0x10)
+            }
+        }
+    }
+
+    struct CountingDelay {
+        total_us: u32,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.total_us += ns / 1_000;
+        }
+    }
+
+    fn retry_bus(failures: u32, config: &RetryConfig) -> RetryBus<FlakyBus, CountingDelay> {
+        let bus = FlakyBus {
+            failures,
+            attempts: 0,
+        };
+        RetryBus::new(bus, CountingDelay { total_us: 0 }, config)
+    }
+
+    #[test]
+    fn test_retries_until_success() {
+        let mut config = RetryConfig::new();
+        config.data_reads(RetryPolicy::new(3, 100));
+        let mut bus = retry_bus(2, &config);
+
+        assert_eq!(bus.read_reg(Register::PROD_ID), Ok(0x10));
+        assert_eq!(
+            bus.stats(),
+            RetryStats {
+                retries: 2,
+                failures: 0
+            }
+        );
+        let (inner, delay) = bus.release();
+        assert_eq!(inner.attempts, 3);
+        assert_eq!(delay.total_us, 200);
+    }
+
+    #[test]
+    fn test_policy_per_operation_class() {
+        let mut config = RetryConfig::new();
+        config.status_polls(RetryPolicy::new(1, 0));
+        let mut bus = retry_bus(3, &config);
+
+        // Config writes are not retried
+        assert_eq!(bus.write_reg(Register::PRS_CFG, 0), Err(()));
+        // Status polls are retried once, then fail
+        assert_eq!(bus.read_reg(Register::MEAS_CFG), Err(()));
+        assert_eq!(
+            bus.stats(),
+            RetryStats {
+                retries: 1,
+                failures: 2
+            }
+        );
+        // Bus recovered
+        let mut buf = [0; 3];
+        assert_eq!(bus.read_many(Register::PSR_B2, &mut buf), Ok(()));
+
+        bus.reset_stats();
+        assert_eq!(bus.stats(), RetryStats::default());
+    }
+}
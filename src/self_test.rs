@@ -0,0 +1,159 @@
+use crate::calibration::{calibrate_pressure, calibrate_temperature, CalibrationCoeffs};
+use crate::chip::{ChipInfo, ChipVariant};
+use crate::device_internal::scale_raw;
+use core::ops::RangeInclusive;
+
+/// Consecutive one-shot samples checked for a stuck result
+pub(crate) const SELF_TEST_SAMPLES: usize = 3;
+/// Datasheet operating range, 300 to 1200 hPa
+const PRESSURE_RANGE_PA: RangeInclusive<f32> = 30_000.0..=120_000.0;
+/// Datasheet operating range
+const TEMPERATURE_RANGE_C: RangeInclusive<f32> = -40.0..=85.0;
+/// Largest accepted difference between the internal and external temperature sensor.
+/// The coefficients are only calibrated for one of them, so the other reads a few degrees off.
+const TEMP_SOURCE_TOLERANCE_C: f32 = 5.0;
+
+/// Result of [`crate::DPS3xx::self_test`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelfTestReport {
+    /// Product and revision read from PROD_ID
+    pub chip: ChipInfo,
+    /// PROD_ID is a supported DPS3xx
+    pub product_ok: bool,
+    /// Coefficients not all zero and each within its on-chip field width, see
+    /// [`CalibrationCoeffs::is_plausible`]. Catches failed reads, not out of spec parts.
+    pub coefficients_ok: bool,
+    /// Every one-shot conversion set its ready flag within [`crate::calc_total_wait_ms`]
+    pub conversions_in_time: bool,
+    /// Last temperature in degrees Celsius, `None` if the conversion did not finish
+    pub temperature_c: Option<f32>,
+    /// Last pressure in Pa, `None` if the conversion did not finish
+    pub pressure_pa: Option<f32>,
+    /// Temperature within -40 to 85 °C
+    pub temperature_in_range: bool,
+    /// Pressure within 300 to 1200 hPa
+    pub pressure_in_range: bool,
+    /// Consecutive raw pressure and temperature results are not all identical
+    pub samples_vary: bool,
+    /// Temperature measured with the other sensor (internal or external) than TEMP_CFG selects
+    pub other_source_temperature_c: Option<f32>,
+    /// Both temperature sensors agree within a few degrees
+    pub temperature_sources_agree: bool,
+}
+
+impl SelfTestReport {
+    /// True if every check passed
+    pub fn passed(&self) -> bool {
+        self.product_ok
+            && self.coefficients_ok
+            && self.conversions_in_time
+            && self.temperature_in_range
+            && self.pressure_in_range
+            && self.samples_vary
+            && self.temperature_sources_agree
+    }
+
+    /// Evaluate the raw one-shot results, `None` where a conversion did not finish
+    pub(crate) fn evaluate(
+        chip: ChipInfo,
+        coeffs: &CalibrationCoeffs,
+        (prs_cfg, temp_cfg): (u8, u8),
+        pres_raw: &[Option<i32>; SELF_TEST_SAMPLES],
+        temp_raw: &[Option<i32>; SELF_TEST_SAMPLES],
+        other_temp_raw: Option<i32>,
+    ) -> Self {
+        let temp_scaled = |raw: Option<i32>| scale_raw(raw?, temp_cfg);
+        let last_temp = temp_scaled(temp_raw.last().copied().flatten());
+        let last_pres = pres_raw
+            .last()
+            .copied()
+            .flatten()
+            .and_then(|raw| scale_raw(raw, prs_cfg));
+
+        let temperature_c = last_temp.map(|temp| calibrate_temperature(coeffs, temp));
+        let pressure_pa = last_pres
+            .zip(last_temp)
+            .map(|(pres, temp)| calibrate_pressure(coeffs, pres, temp));
+        let other_source_temperature_c =
+            temp_scaled(other_temp_raw).map(|temp| calibrate_temperature(coeffs, temp));
+
+        let conversions_in_time =
+            pres_raw.iter().chain(temp_raw).all(Option::is_some) && other_temp_raw.is_some();
+
+        Self {
+            chip,
+            product_ok: chip.variant != ChipVariant::Unknown,
+            coefficients_ok: coeffs.is_plausible(),
+            conversions_in_time,
+            temperature_c,
+            pressure_pa,
+            temperature_in_range: temperature_c.is_some_and(|t| TEMPERATURE_RANGE_C.contains(&t)),
+            pressure_in_range: pressure_pa.is_some_and(|p| PRESSURE_RANGE_PA.contains(&p)),
+            samples_vary: varies(pres_raw) && varies(temp_raw),
+            other_source_temperature_c,
+            temperature_sources_agree: temperature_c.zip(other_source_temperature_c).is_some_and(
+                |(t, other)| {
+                    // f32::abs is not in core before Rust 1.85
+                    (-TEMP_SOURCE_TOLERANCE_C..=TEMP_SOURCE_TOLERANCE_C).contains(&(t - other))
+                },
+            ),
+        }
+    }
+}
+
+/// False if all results are equal, a stuck ADC or bus keeps returning the same value
+fn varies(raw: &[Option<i32>]) -> bool {
+    let mut values = raw.iter().flatten();
+    match values.next() {
+        Some(first) => values.any(|value| value != first),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// C0 = 40 (20 degrees Celsius), C1 = -260, C00 = 100000 (Pa)
+    fn coeffs() -> CalibrationCoeffs {
+        CalibrationCoeffs::decode(&[
+            0x02, 0x8E, 0xFC, 0x18, 0x6A, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ])
+    }
+
+    #[test]
+    fn test_healthy_sensor_passes() {
+        let report = SelfTestReport::evaluate(
+            ChipInfo::from_id(0x10),
+            &coeffs(),
+            (0, 0),
+            &[Some(10), Some(12), Some(11)],
+            &[Some(0), Some(2), Some(1)],
+            Some(-2048),
+        );
+        assert!(report.passed(), "{report:?}");
+        assert_eq!(report.pressure_pa, Some(100_000.0));
+    }
+
+    #[test]
+    fn test_failures_are_reported() {
+        let report = SelfTestReport::evaluate(
+            ChipInfo::from_id(0x20),
+            &CalibrationCoeffs::default(),
+            (0, 0),
+            &[Some(7), Some(7), Some(7)],
+            &[Some(0), None, Some(1)],
+            None,
+        );
+        assert!(!report.product_ok);
+        assert!(!report.coefficients_ok);
+        assert!(!report.conversions_in_time);
+        assert!(!report.samples_vary);
+        assert!(!report.temperature_sources_agree);
+        // Default coefficients give 0 Pa
+        assert!(!report.pressure_in_range);
+        assert!(!report.passed());
+    }
+}
@@ -0,0 +1,167 @@
+//! Hand-written serde impls: rate/resolution enums as readable names that also accept the
+//! numeric register value, and [`Config`] deserialization that runs the config checks.
+
+use crate::config::{
+    Config, ConfigError, PressureRate, PressureResolution, TemperatureRate, TemperatureResolution,
+};
+use core::fmt;
+use serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use serde::{Serialize, Serializer};
+
+macro_rules! register_enum_serde {
+    ($ty:ident, $expecting:literal, [$($variant:ident => $name:literal),* $(,)?]) => {
+        impl Serialize for $ty {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                if !serializer.is_human_readable() {
+                    return serializer.serialize_u8(*self as u8);
+                }
+                let name = match self {
+                    $($ty::$variant => $name,)*
+                };
+                serializer.serialize_str(name)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct EnumVisitor;
+
+                impl Visitor<'_> for EnumVisitor {
+                    type Value = $ty;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.write_str($expecting)
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<$ty, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value.strip_prefix('_').unwrap_or(value) {
+                            $($name => Ok($ty::$variant),)*
+                            _ => Err(E::unknown_variant(value, &[$($name),*])),
+                        }
+                    }
+
+                    fn visit_u64<E>(self, value: u64) -> Result<$ty, E>
+                    where
+                        E: de::Error,
+                    {
+                        $(
+                            if value == $ty::$variant as u64 {
+                                return Ok($ty::$variant);
+                            }
+                        )*
+                        Err(E::invalid_value(Unexpected::Unsigned(value), &self))
+                    }
+
+                    fn visit_i64<E>(self, value: i64) -> Result<$ty, E>
+                    where
+                        E: de::Error,
+                    {
+                        match u64::try_from(value) {
+                            Ok(value) => self.visit_u64(value),
+                            Err(_) => Err(E::invalid_value(Unexpected::Signed(value), &self)),
+                        }
+                    }
+                }
+
+                if deserializer.is_human_readable() {
+                    // Human readable formats are self-describing, so a name or a register
+                    // value can be told apart. Binary ones only carry the register value.
+                    deserializer.deserialize_any(EnumVisitor)
+                } else {
+                    deserializer.deserialize_u8(EnumVisitor)
+                }
+            }
+        }
+    };
+}
+
+register_enum_serde!(PressureRate, "pressure rate name or register value 0..=7", [
+    _1_SPS => "1_SPS",
+    _2_SPS => "2_SPS",
+    _4_SPS => "4_SPS",
+    _8_SPS => "8_SPS",
+    _16_SPS => "16_SPS",
+    _32_SPS => "32_SPS",
+    _64_SPS => "64_SPS",
+    _128_SPS => "128_SPS",
+]);
+
+register_enum_serde!(TemperatureRate, "temperature rate name or register value 0..=7", [
+    _1_SPS => "1_SPS",
+    _2_SPS => "2_SPS",
+    _4_SPS => "4_SPS",
+    _8_SPS => "8_SPS",
+    _16_SPS => "16_SPS",
+    _32_SPS => "32_SPS",
+    _64_SPS => "64_SPS",
+    _128_SPS => "128_SPS",
+]);
+
+register_enum_serde!(PressureResolution, "pressure resolution name or register value 0..=7", [
+    _1_SAMPLES => "1_SAMPLES",
+    _2_SAMPLES => "2_SAMPLES",
+    _4_SAMPLES => "4_SAMPLES",
+    _8_SAMPLES => "8_SAMPLES",
+    _16_SAMPLES => "16_SAMPLES",
+    _32_SAMPLES => "32_SAMPLES",
+    _64_SAMPLES => "64_SAMPLES",
+    _128_SAMPLES => "128_SAMPLES",
+]);
+
+register_enum_serde!(TemperatureResolution, "temperature resolution name or register value 0..=7", [
+    _1_SAMPLES => "1_SAMPLES",
+    _2_SAMPLES => "2_SAMPLES",
+    _4_SAMPLES => "4_SAMPLES",
+    _8_SAMPLES => "8_SAMPLES",
+    _16_SAMPLES => "16_SAMPLES",
+    _32_SAMPLES => "32_SAMPLES",
+    _64_SAMPLES => "64_SAMPLES",
+    _128_SAMPLES => "128_SAMPLES",
+]);
+
+/// Field-wise [`Config`] deserialization, missing fields take the [`Config::new`] defaults
+#[derive(serde::Deserialize)]
+#[serde(remote = "Config", default = "Config::new")]
+struct ConfigFields {
+    pres_rate: Option<PressureRate>,
+    pres_res: Option<PressureResolution>,
+    temp_rate: Option<TemperatureRate>,
+    temp_res: Option<TemperatureResolution>,
+    temp_ext: Option<bool>,
+    int_hl: bool,
+    int_fifo: bool,
+    int_temp: bool,
+    int_pres: bool,
+    temp_shift: bool,
+    pres_shift: bool,
+    fifo_enable: bool,
+    spi_mode: bool,
+    init_timeout_ms: u32,
+    temp_workaround: Option<bool>,
+    coeff_double_read: bool,
+    coeff_retries: u8,
+}
+
+/// [`Config`] as written, before the checks run by [`Config`]'s `try_from`
+#[derive(serde::Deserialize)]
+#[serde(transparent)]
+pub(crate) struct UncheckedConfig(#[serde(with = "ConfigFields")] Config);
+
+/// Rejects combinations that the mode independent checks of [`Config::validate`] flag
+impl TryFrom<UncheckedConfig> for Config {
+    type Error = ConfigError;
+
+    fn try_from(UncheckedConfig(config): UncheckedConfig) -> Result<Self, ConfigError> {
+        config.check(None, false)?;
+        Ok(config)
+    }
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "async")]
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_mock::eh1::digital::{
+    Mock as PinMock, State as PinState, Transaction as PinTransaction,
+};
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use pollster::block_on;
+use uf_dps3xx::{Config, DPS3xxAsync, Error, InitStage, MeasurementMode, Register};
+
+pub mod common;
+
+use common::{Access, TEST_COEFFS};
+
+const ADDR: u8 = 0x77;
+
+struct TestDelay;
+
+impl DelayNs for TestDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
+fn init_expectations() -> Vec<I2cTransaction> {
+    common::init_and_calibrate(0x00, &TEST_COEFFS)
+        .into_iter()
+        .map(|access| match access {
+            Access::Read(reg, response) => {
+                I2cTransaction::write_read(ADDR, vec![reg.addr()], response)
+            }
+            Access::Write(addr, value) => I2cTransaction::write(ADDR, vec![addr, value]),
+        })
+        .collect()
+}
+
+#[test]
+fn test_async_init_and_calibrate() {
+    let expectations = init_expectations();
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xxAsync::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut delay = TestDelay;
+
+    let _dps = block_on(dps.init_and_calibrate(&mut delay)).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn test_async_measure_and_read() {
+    let mut expectations = init_expectations();
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0x00]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x01]),
+        I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::PSR_B2.addr()], vec![0x00, 0x04, 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x04, 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xxAsync::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut delay = TestDelay;
+    let mut dps = block_on(dps.init_and_calibrate(&mut delay)).unwrap();
+
+    block_on(dps.start_measurement(MeasurementMode::OneShotPressure)).unwrap();
+    assert_eq!(block_on(dps.read_pressure_calibrated()).unwrap(), 100_000.0);
+    assert_eq!(block_on(dps.read_temp_calibrated()).unwrap(), 20.0);
+    i2c.done();
+}
+
+#[test]
+fn test_async_init_and_calibrate_timeout_waiting_init_complete() {
+    let mut expectations = init_expectations();
+    expectations.truncate(13);
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::MEAS_CFG.addr()],
+        vec![0x00],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut config = Config::new();
+    config.init_timeout_ms(5);
+
+    let dps = DPS3xxAsync::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut delay = TestDelay;
+    assert!(matches!(
+        block_on(dps.init_and_calibrate(&mut delay)),
+        Err(Error::InitTimeout(InitStage::WaitingInitComplete))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn test_async_wait_for_interrupt() {
+    let mut expectations = init_expectations();
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::INT_STS.addr()], vec![0x03]),
+        I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::PSR_B2.addr()], vec![0x00, 0x00, 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
+    ]);
+    let pin_expectations = [PinTransaction::wait_for_state(PinState::Low)];
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut pin = PinMock::new(&pin_expectations);
+    let config = Config::new();
+    let dps = DPS3xxAsync::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut delay = TestDelay;
+    let mut dps = block_on(dps.init_and_calibrate(&mut delay)).unwrap();
+
+    let sample = block_on(dps.wait_for_interrupt(&mut pin)).unwrap();
+    assert!(sample.status.pres_ready && sample.status.temp_ready);
+    assert_eq!(sample.pressure, Some(100_000.0));
+    assert_eq!(sample.temperature, Some(20.0));
+    i2c.done();
+    pin.done();
+}
@@ -0,0 +1,73 @@
+//! Fixtures shared by the integration tests, independent of the bus and mock used.
+//!
+//! Test files declare it as `pub mod common` so helpers they do not use are not dead code.
+
+use uf_dps3xx::Register;
+
+/// C0 = 40 (20 degrees Celsius), C00 = 100000 (Pa), all other coefficients 0
+pub const TEST_COEFFS: [u8; 18] = [
+    0x02, 0x80, 0x00, 0x18, 0x6A, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// One register access expected by a test, mapped to transactions by each test file
+#[derive(Clone, Debug)]
+pub enum Access {
+    /// Read starting at the register, returning the bytes
+    Read(Register, Vec<u8>),
+    /// Write of a value to a register address
+    Write(u8, u8),
+}
+
+fn read(reg: Register, response: &[u8]) -> Access {
+    Access::Read(reg, response.to_vec())
+}
+
+fn write(reg: Register, value: u8) -> Access {
+    Access::Write(reg.addr(), value)
+}
+
+/// `start_init` with the default config apart from the CFG_REG value written
+pub fn start_init(cfg_reg: u8) -> Vec<Access> {
+    vec![
+        read(Register::PROD_ID, &[0x10]),
+        read(Register::PRS_CFG, &[0x00]),
+        write(Register::PRS_CFG, 0x00),
+        read(Register::TEMP_CFG, &[0x00]),
+        read(Register::TMP_COEF_SRCE, &[0x00]),
+        write(Register::TEMP_CFG, 0x00),
+        write(Register::CFG_REG, cfg_reg),
+        write(Register::MEAS_CFG, 0x00),
+        Access::Write(0x0E, 0xA5),
+        Access::Write(0x0F, 0x96),
+        Access::Write(0x62, 0x02),
+        Access::Write(0x0E, 0x00),
+        Access::Write(0x0F, 0x00),
+    ]
+}
+
+/// First `poll_init`: init complete, start the init temperature measurement
+pub fn first_poll() -> Vec<Access> {
+    vec![
+        read(Register::MEAS_CFG, &[0x40]),
+        write(Register::MEAS_CFG, 0x02),
+    ]
+}
+
+/// Second `poll_init` to calibrated, coefficients already available
+pub fn second_poll(coeffs: &[u8]) -> Vec<Access> {
+    vec![
+        read(Register::MEAS_CFG, &[0x60]),
+        read(Register::TMP_B2, &[0x00, 0x00, 0x00]),
+        write(Register::MEAS_CFG, 0x00),
+        read(Register::MEAS_CFG, &[0x80]),
+        read(Register::COEFF_REG_1, coeffs),
+    ]
+}
+
+/// `init_and_calibrate` with the default config apart from CFG_REG
+pub fn init_and_calibrate(cfg_reg: u8, coeffs: &[u8]) -> Vec<Access> {
+    let mut accesses = start_init(cfg_reg);
+    accesses.extend(first_poll());
+    accesses.extend(second_poll(coeffs));
+    accesses
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "eh02")]
+
+use embedded_hal_mock::eh0::delay::NoopDelay;
+use embedded_hal_mock::eh0::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use embedded_hal_mock::eh0::MockError;
+use std::io::ErrorKind;
+use uf_dps3xx::{Config, DPS3xx, Eh02Delay, Eh02I2c, Eh02I2cError, Error, Register};
+
+pub mod common;
+
+use common::{Access, TEST_COEFFS};
+
+const ADDR: u8 = 0x77;
+
+/// Transactions of `init_and_calibrate` with the default config
+fn calibrated_expectations() -> Vec<I2cTransaction> {
+    common::init_and_calibrate(0x00, &TEST_COEFFS)
+        .into_iter()
+        .map(|access| match access {
+            Access::Read(reg, response) => {
+                I2cTransaction::write_read(ADDR, vec![reg.addr()], response)
+            }
+            Access::Write(addr, value) => I2cTransaction::write(ADDR, vec![addr, value]),
+        })
+        .collect()
+}
+
+#[test]
+fn test_init_and_calibrate_with_eh02_peripherals() {
+    let mut expectations = calibrated_expectations();
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PSR_B2.addr()],
+        vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    ));
+
+    let i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(Eh02I2c::new(i2c), ADDR, &config).unwrap();
+    let mut delay = Eh02Delay::new(NoopDelay::new());
+
+    let mut dps = dps.init_and_calibrate(&mut delay).unwrap();
+    let measurement = dps.read_sample().unwrap();
+    assert_eq!(measurement.pressure_pa, 100_000.0);
+    assert_eq!(measurement.temperature_c, 20.0);
+
+    let mut i2c = dps.release().release();
+    i2c.done();
+}
+
+#[test]
+fn test_eh02_errors_map_to_i2c_error() {
+    let expectations =
+        [
+            I2cTransaction::write_read(ADDR, vec![Register::PROD_ID.addr()], vec![0x10])
+                .with_error(MockError::Io(ErrorKind::Other)),
+        ];
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(Eh02I2c::new(i2c.clone()), ADDR, &config).unwrap();
+
+    assert!(matches!(
+        dps.start_init(),
+        Err(Error::I2CError(Eh02I2cError::I2c(MockError::Io(
+            ErrorKind::Other
+        ))))
+    ));
+    i2c.done();
+}
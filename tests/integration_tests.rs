@@ -1,11 +1,24 @@
-use embedded_hal::{delay::DelayNs, i2c::I2c};
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{ErrorKind, I2c},
+};
+use embedded_hal_mock::eh1::digital::{
+    Mock as PinMock, State as PinState, Transaction as PinTransaction,
+};
 use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
 use uf_dps3xx::{
-    calc_total_wait_ms, Config, Configured, DPS3xx, Error, InitInProgress, InitPoll, InitStage,
-    MeasurementMode, PressureRate, PressureResolution, Register, TemperatureRate,
-    TemperatureResolution,
+    calc_total_wait_ms, Bus, CalibrationCoeffs, CfgReg, ChipVariant, Config, ConfigError,
+    Configured, DPS3xx, DPS3xxI2c, Error, FifoEntry, FifoSample, FifoStatus, I2cBus,
+    InitInProgress, InitPoll, InitStage, InterruptStatus, MeasCfg, MeasurementMode, PressureRate,
+    PressureResolution, Register, ResetReg, RetryConfig, RetryPolicy, RetryStats, TemperatureRate,
+    TemperatureResolution, Timestamped, Unconfigured, PRESSURE_FIXED_SCALE,
+    TEMPERATURE_FIXED_SCALE,
 };
 
+pub mod common;
+
+use common::{Access, TEST_COEFFS};
+
 const ADDR: u8 = 0x77;
 
 struct TestDelay;
@@ -14,7 +27,8 @@ impl DelayNs for TestDelay {
     fn delay_ns(&mut self, _ns: u32) {}
 }
 
-fn finish_init<I2C>(dps: DPS3xx<I2C, InitInProgress>) -> DPS3xx<I2C, Configured>
+#[cfg(test)]
+fn finish_init<I2C>(dps: DPS3xx<I2cBus<I2C>, InitInProgress>) -> DPS3xx<I2cBus<I2C>, Configured>
 where
     I2C: I2c,
 {
@@ -24,7 +38,8 @@ where
     }
 }
 
-fn poll_init_ready<I2C>(dps: &mut DPS3xx<I2C, InitInProgress>)
+#[cfg(test)]
+fn poll_init_ready<I2C>(dps: &mut DPS3xx<I2cBus<I2C>, InitInProgress>)
 where
     I2C: I2c,
 {
@@ -32,6 +47,23 @@ where
     assert!(matches!(dps.poll_init().unwrap(), InitPoll::Ready));
 }
 
+fn i2c_transactions(accesses: Vec<Access>) -> Vec<I2cTransaction> {
+    accesses
+        .into_iter()
+        .map(|access| match access {
+            Access::Read(reg, response) => {
+                I2cTransaction::write_read(ADDR, vec![reg.addr()], response)
+            }
+            Access::Write(addr, value) => I2cTransaction::write(ADDR, vec![addr, value]),
+        })
+        .collect()
+}
+
+/// Transactions of `init_and_calibrate` with the default config
+fn calibrated_expectations(coeffs: &[u8]) -> Vec<I2cTransaction> {
+    i2c_transactions(common::init_and_calibrate(0x00, coeffs))
+}
+
 #[test]
 fn test_new_dps3xx_defaults() {
     let expectations = [
@@ -164,7 +196,11 @@ fn test_read_calibration_coefficients() {
         I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
         I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
         I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0x80]),
-        I2cTransaction::write_read(ADDR, vec![Register::COEFF_REG_1.addr()], vec![0; 18]),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
     ];
 
     let mut i2c = I2cMock::new(&expectations);
@@ -216,6 +252,78 @@ fn test_read_calibration_coefficients_requires_coef_ready() {
     i2c.done();
 }
 
+#[test]
+fn test_init_and_calibrate_rereads_corrupted_coefficients() {
+    let mut expectations = calibrated_expectations(&[0xFF; 18]);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::COEFF_REG_1.addr()], vec![0; 18]),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+
+    let dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+    assert_eq!(dps.calibration_coefficients().encode(), TEST_COEFFS);
+    i2c.done();
+}
+
+#[test]
+fn test_init_and_calibrate_rejects_stuck_bus_coefficients() {
+    let stuck = [0xFF; 18];
+    let mut expectations = calibrated_expectations(&stuck);
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::COEFF_REG_1.addr()],
+        stuck.to_vec(),
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut config = Config::new();
+    config.coeff_retries(1);
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+
+    assert!(matches!(
+        dps.init_and_calibrate(&mut TestDelay),
+        Err(Error::InvalidCoefficients)
+    ));
+    i2c.done();
+}
+
+#[test]
+fn test_coefficient_double_read_must_match() {
+    let mut glitched = TEST_COEFFS;
+    glitched[4] ^= 0x01;
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::COEFF_REG_1.addr()], glitched.to_vec()),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut config = Config::new();
+    config.coeff_double_read(true);
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+
+    let dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+    assert_eq!(dps.calibration_coefficients().encode(), TEST_COEFFS);
+    i2c.done();
+}
+
 #[test]
 fn test_init_and_calibrate() {
     let expectations = [
@@ -238,7 +346,11 @@ fn test_init_and_calibrate() {
         I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
         I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
         I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0x80]),
-        I2cTransaction::write_read(ADDR, vec![Register::COEFF_REG_1.addr()], vec![0; 18]),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
     ];
 
     let mut i2c = I2cMock::new(&expectations);
@@ -287,6 +399,213 @@ fn test_start_measurement() {
     i2c.done();
 }
 
+#[test]
+fn test_restore_calibration_skips_coefficient_read() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    // Second boot: init without waiting for COEF_RDY, restore from the saved blob
+    expectations.extend_from_slice(&calibrated_expectations(&TEST_COEFFS)[..18]);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::PROD_ID.addr()], vec![0x10]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+    let coeffs = dps.calibration_coefficients();
+    assert_eq!(coeffs.encode(), TEST_COEFFS);
+    let blob = coeffs.to_blob();
+    let i2c_bus = dps.release();
+
+    let dps = DPS3xx::new(i2c_bus, ADDR, &config).unwrap();
+    let mut dps = dps.start_init().unwrap();
+    poll_init_ready(&mut dps);
+    let dps = finish_init(dps);
+    let restored = CalibrationCoeffs::from_blob(&blob).unwrap();
+    let mut dps = dps.restore_calibration(&restored).unwrap();
+
+    assert_eq!(dps.read_temp_calibrated().unwrap(), 20.0);
+    i2c.done();
+}
+
+#[test]
+fn test_restore_calibration_rejects_unknown_product() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS)[..18].to_vec();
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PROD_ID.addr()],
+        vec![0x20],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.start_init().unwrap();
+    poll_init_ready(&mut dps);
+    let dps = finish_init(dps);
+
+    let coeffs = CalibrationCoeffs::decode(&TEST_COEFFS);
+    assert!(matches!(
+        dps.restore_calibration(&coeffs),
+        Err(Error::InvalidProductId)
+    ));
+    i2c.done();
+}
+
+/// One-shot conversion in `self_test`, `meas_ctrl` started and `ready` reported in MEAS_CFG
+fn one_shot_expectations(
+    meas_ctrl: u8,
+    ready: u8,
+    result: Register,
+    raw: u8,
+) -> [I2cTransaction; 4] {
+    [
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xC0]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0xC0 | meas_ctrl]),
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xC0 | ready]),
+        I2cTransaction::write_read(ADDR, vec![result.addr()], vec![0x00, 0x00, raw]),
+    ]
+}
+
+#[test]
+fn test_self_test() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::PROD_ID.addr()], vec![0x10]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
+    ]);
+    for raw in 1..=3 {
+        expectations.extend(one_shot_expectations(0x02, 0x20, Register::TMP_B2, raw));
+        expectations.extend(one_shot_expectations(0x01, 0x10, Register::PSR_B2, raw));
+    }
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write(ADDR, vec![Register::TEMP_CFG.addr(), 0x80]),
+    ]);
+    expectations.extend(one_shot_expectations(0x02, 0x20, Register::TMP_B2, 4));
+    expectations.push(I2cTransaction::write(
+        ADDR,
+        vec![Register::TEMP_CFG.addr(), 0x00],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let report = dps.self_test(&mut TestDelay).unwrap();
+    assert!(report.passed(), "{report:?}");
+    assert_eq!(report.temperature_c, Some(20.0));
+    assert_eq!(report.pressure_pa, Some(100_000.0));
+    i2c.done();
+}
+
+#[test]
+fn test_self_test_reports_stuck_conversion() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::PROD_ID.addr()], vec![0x10]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
+    ]);
+    for _ in 1..=3 {
+        expectations.extend(one_shot_expectations(0x02, 0x20, Register::TMP_B2, 1));
+        // Pressure never gets ready, the conversion is aborted
+        expectations.extend([
+            I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xC0]),
+            I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0xC1]),
+            I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xC1]),
+            I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
+        ]);
+    }
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write(ADDR, vec![Register::TEMP_CFG.addr(), 0x80]),
+    ]);
+    expectations.extend(one_shot_expectations(0x02, 0x20, Register::TMP_B2, 2));
+    expectations.push(I2cTransaction::write(
+        ADDR,
+        vec![Register::TEMP_CFG.addr(), 0x00],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let report = dps.self_test(&mut TestDelay).unwrap();
+    assert!(!report.passed(), "pressure conversion never finished");
+    assert!(!report.conversions_in_time);
+    assert!(!report.samples_vary);
+    assert_eq!(report.pressure_pa, None);
+    assert!(report.temperature_sources_agree);
+    i2c.done();
+}
+
+#[test]
+fn test_measure_both_takes_temperature_first() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xC0]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0xC2]),
+        // Not ready after the computed wait, polled again
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xC0]),
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xE0]),
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xE0]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0xE1]),
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xF0]),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::PSR_B2.addr()],
+            vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let measurement = dps.measure_both(&mut TestDelay).unwrap();
+    assert_eq!(measurement.pressure_pa, 100_000.0);
+    assert_eq!(measurement.temperature_c, 20.0);
+    i2c.done();
+}
+
+#[test]
+fn test_measure_pressure_times_out() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xC0]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0xC1]),
+    ]);
+    // Polled once after the 13 ms wait and then every ms for another 13 ms
+    let wait_ms = calc_total_wait_ms(0, 0);
+    for _ in 0..=wait_ms {
+        expectations.push(I2cTransaction::write_read(
+            ADDR,
+            vec![Register::MEAS_CFG.addr()],
+            vec![0xC1],
+        ));
+    }
+    expectations.push(I2cTransaction::write(
+        ADDR,
+        vec![Register::MEAS_CFG.addr(), 0x00],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    assert!(matches!(
+        dps.measure_pressure(&mut TestDelay),
+        Err(Error::MeasurementTimeout)
+    ));
+    i2c.done();
+}
+
 #[test]
 fn test_read_temp_calibrated() {
     let expectations = [
@@ -309,7 +628,11 @@ fn test_read_temp_calibrated() {
         I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
         I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
         I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0x80]),
-        I2cTransaction::write_read(ADDR, vec![Register::COEFF_REG_1.addr()], vec![0; 18]),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
         I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
         I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
     ];
@@ -326,6 +649,50 @@ fn test_read_temp_calibrated() {
     i2c.done();
 }
 
+#[test]
+fn test_read_sample_timestamped_backdates_background_results() {
+    let burst = I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PSR_B2.addr()],
+        vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    // 64 times oversampling with P_SHIFT
+    expectations[2] = I2cTransaction::write(ADDR, vec![Register::PRS_CFG.addr(), 0x06]);
+    expectations[6] = I2cTransaction::write(ADDR, vec![Register::CFG_REG.addr(), 0x04]);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xF7]),
+        burst.clone(),
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xF6]),
+        burst.clone(),
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xF0]),
+        burst,
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut config = Config::new();
+    config.pres_res(PressureResolution::_64_SAMPLES);
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let mut now_us = 1_000_000;
+    let mut clock = || {
+        now_us += 1_000;
+        now_us
+    };
+    // Background mode: 64 times oversampling takes 104.4 ms
+    let sample = dps.read_sample_timestamped(&mut clock).unwrap();
+    assert_eq!(sample.timestamp_us, 1_001_000 - 104_400);
+    assert_eq!(sample.value.pressure_pa, 100_000.0);
+    // Background temperature: single sample temperature conversion takes 3.6 ms
+    let sample = dps.read_sample_timestamped(&mut clock).unwrap();
+    assert_eq!(sample.timestamp_us, 1_002_000 - 3_600);
+    // Idle: time of the read
+    let sample = dps.read_sample_timestamped(&mut clock).unwrap();
+    assert_eq!(sample.timestamp_us, 1_003_000);
+    i2c.done();
+}
+
 #[test]
 fn test_status_and_ready_flags() {
     let expectations = [
@@ -392,7 +759,11 @@ fn test_read_pressure_calibrated() {
         I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
         I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
         I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0x80]),
-        I2cTransaction::write_read(ADDR, vec![Register::COEFF_REG_1.addr()], vec![0; 18]),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
         I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
         I2cTransaction::write_read(ADDR, vec![Register::PSR_B2.addr()], vec![0x00, 0x04, 0x00]), // 1024
         I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
@@ -408,10 +779,10 @@ fn test_read_pressure_calibrated() {
     let mut dps = dps.read_calibration_coefficients().unwrap();
 
     let pres = dps.read_pressure_calibrated().unwrap();
-    // With all coeffs 0 and raw values 1024, scaled values 1024/524288 = 0.001953125
+    // With only C00 set and raw values 1024, scaled values 1024/524288 = 0.001953125
     // pres_cal = C00 + pres_scaled * (C10 + pres_scaled * (C20 + pres_scaled * C30)) + ...
-    // = 0 + 0.00195... * (0 + ...) = 0
-    assert_eq!(pres, 0.0);
+    // = 100000 + 0.00195... * (0 + ...) = 100000
+    assert_eq!(pres, 100_000.0);
     i2c.done();
 }
 
@@ -462,6 +833,71 @@ fn test_release() {
     released_i2c.done();
 }
 
+/// Generic over the transport, as downstream code can write it
+fn product_id<B: Bus, S>(dps: &mut DPS3xx<B, S>) -> Result<u8, Error<B::Error>> {
+    dps.get_product_id()
+}
+
+#[test]
+fn test_generic_code_over_bus_and_type_alias() {
+    let expectations = [I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PROD_ID.addr()],
+        vec![0x10],
+    )];
+
+    let i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let mut dps: DPS3xxI2c<I2cMock, Unconfigured> =
+        DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+
+    assert_eq!(product_id(&mut dps).unwrap(), 0x10);
+    dps.release().done();
+}
+
+#[test]
+fn test_retry_recovers_transient_bus_errors() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    // NACK on the status poll waiting for init complete and on the product ID read
+    expectations.insert(13, expectations[13].clone().with_error(ErrorKind::Other));
+    expectations.insert(0, expectations[0].clone().with_error(ErrorKind::Other));
+    // Config writes are not retried
+    expectations.push(
+        I2cTransaction::write(ADDR, vec![Register::CFG_REG.addr(), 0x00])
+            .with_error(ErrorKind::Other),
+    );
+
+    let i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let mut retry = RetryConfig::new();
+    retry
+        .status_polls(RetryPolicy::new(1, 0))
+        .data_reads(RetryPolicy::new(2, 100));
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config)
+        .unwrap()
+        .with_retry(&retry, TestDelay);
+
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+    assert_eq!(
+        dps.retry_stats(),
+        RetryStats {
+            retries: 2,
+            failures: 0
+        }
+    );
+
+    assert!(matches!(
+        dps.write_register(CfgReg::default()),
+        Err(Error::I2CError(_))
+    ));
+    assert_eq!(dps.retry_stats().failures, 1);
+    dps.reset_retry_stats();
+    assert_eq!(dps.retry_stats(), RetryStats::default());
+
+    let (mut released_i2c, _delay) = dps.release();
+    released_i2c.done();
+}
+
 #[test]
 fn test_start_init_accepts_revision_variants() {
     let expectations = [
@@ -488,6 +924,153 @@ fn test_start_init_accepts_revision_variants() {
     i2c.done();
 }
 
+#[test]
+fn test_start_init_temp_workaround_override() {
+    let expectations = [
+        I2cTransaction::write_read(ADDR, vec![Register::PROD_ID.addr()], vec![0x1F]),
+        I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
+        I2cTransaction::write(ADDR, vec![Register::PRS_CFG.addr(), 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_COEF_SRCE.addr()], vec![0x00]),
+        I2cTransaction::write(ADDR, vec![Register::TEMP_CFG.addr(), 0x00]),
+        I2cTransaction::write(ADDR, vec![Register::CFG_REG.addr(), 0x00]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
+    ];
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut config = Config::new();
+    config.temp_workaround(false);
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+
+    assert!(dps.start_init().is_ok());
+    i2c.done();
+}
+
+#[test]
+fn test_chip_info() {
+    let expectations = [I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PROD_ID.addr()],
+        vec![0x10],
+    )];
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let mut dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+
+    let chip = dps.chip_info().unwrap();
+    assert_eq!(chip.variant, ChipVariant::Dps3xx);
+    assert_eq!(chip.product, 0);
+    assert_eq!(chip.revision, 1);
+    i2c.done();
+}
+
+#[test]
+fn test_typed_register_access() {
+    let expectations = [
+        I2cTransaction::write_read(ADDR, vec![Register::CFG_REG.addr()], vec![0x04]),
+        I2cTransaction::write(ADDR, vec![Register::CFG_REG.addr(), 0x24]),
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xF7]),
+        I2cTransaction::write(ADDR, vec![Register::RESET.addr(), 0x80]),
+    ];
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let mut dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+
+    dps.modify_register(|cfg: CfgReg| CfgReg {
+        int_temp: true,
+        ..cfg
+    })
+    .unwrap();
+    let meas_cfg: MeasCfg = dps.read_register().unwrap();
+    assert!(meas_cfg.coef_ready && meas_cfg.pres_ready);
+    assert_eq!(
+        meas_cfg.mode,
+        Some(MeasurementMode::BackgroundPressureAndTemperature)
+    );
+    dps.write_register(ResetReg::FLUSH_FIFO).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn test_register_dump_in_any_state() {
+    let block = vec![
+        0x00, 0x04, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x01, 0x00, 0x10,
+    ];
+    let expectations = [
+        I2cTransaction::write_read(ADDR, vec![Register::PSR_B2.addr()], block),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_COEF_SRCE.addr()], vec![0x80]),
+    ];
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let mut dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+
+    let dump = dps.register_dump().unwrap();
+    assert_eq!(dump.pressure_raw, 1024);
+    assert_eq!(dump.temperature_raw, 2048);
+    assert!(dump.meas_cfg.init_complete && !dump.meas_cfg.coef_ready);
+    assert!(dump.fifo_sts.empty);
+    assert_eq!(dump.chip.variant, ChipVariant::Dps3xx);
+    assert_eq!(dump.coefficients, CalibrationCoeffs::decode(&TEST_COEFFS));
+    // TEMP_CFG still selects the internal sensor although the coefficients use the external one
+    let diff = dump.diff(&config);
+    assert_eq!(diff.temp_cfg, 0x80);
+    assert_eq!(diff.prs_cfg | diff.cfg_reg, 0);
+    i2c.done();
+}
+
+#[test]
+fn test_register_dump_after_poll_init_timeout() {
+    let mut expectations = i2c_transactions(common::start_init(0x00));
+    // INIT_RDY never set
+    for _ in 0..3 {
+        expectations.push(I2cTransaction::write_read(
+            ADDR,
+            vec![Register::MEAS_CFG.addr()],
+            vec![0x00],
+        ));
+    }
+    expectations.extend([
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::PSR_B2.addr()],
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10],
+        ),
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::COEFF_REG_1.addr()],
+            TEST_COEFFS.to_vec(),
+        ),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_COEF_SRCE.addr()], vec![0x00]),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.start_init().unwrap();
+
+    let mut polls = 0;
+    while let InitPoll::Pending(_) = dps.poll_init().unwrap() {
+        polls += 1;
+        if polls == 3 {
+            break;
+        }
+    }
+    assert_eq!(polls, 3);
+
+    let dump = dps.register_dump().unwrap();
+    assert!(!dump.meas_cfg.init_complete);
+    assert_eq!(dump.chip.variant, ChipVariant::Dps3xx);
+    i2c.done();
+}
+
 #[test]
 fn test_start_init_rejects_wrong_product_family() {
     let expectations = [I2cTransaction::write_read(
@@ -651,3 +1234,452 @@ fn test_init_and_calibrate_timeout_waiting_coef_ready() {
     ));
     i2c.done();
 }
+
+#[test]
+fn test_fifo_status() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::FIFO_STS.addr()], vec![0x01]),
+        I2cTransaction::write_read(ADDR, vec![Register::FIFO_STS.addr()], vec![0x02]),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    assert_eq!(
+        dps.fifo_status().unwrap(),
+        FifoStatus {
+            full: false,
+            empty: true
+        }
+    );
+    assert_eq!(
+        dps.fifo_status().unwrap(),
+        FifoStatus {
+            full: true,
+            empty: false
+        }
+    );
+    i2c.done();
+}
+
+#[test]
+fn test_read_fifo_raw_stops_at_empty_marker() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PSR_B2.addr()],
+        vec![
+            0x00, 0x10, 0x00, // temperature
+            0x00, 0x20, 0x01, // pressure
+            0x80, 0x00, 0x00, // empty
+            0x00, 0x00, 0x00,
+        ],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let mut entries = [FifoEntry::Empty; 4];
+    assert_eq!(dps.read_fifo_raw(&mut entries).unwrap(), 2);
+    assert_eq!(
+        entries,
+        [
+            FifoEntry::Temperature(0x1000),
+            FifoEntry::Pressure(0x2001),
+            FifoEntry::Empty,
+            FifoEntry::Empty,
+        ]
+    );
+    i2c.done();
+}
+
+#[test]
+fn test_read_fifo_compensates_samples() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PSR_B2.addr()],
+        vec![
+            0x00, 0x20, 0x01, // pressure, compensated with the following temperature
+            0x00, 0x10, 0x00, // temperature
+            0x80, 0x00, 0x00, // empty
+        ],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let mut samples = [FifoSample::Temperature(0.0); 3];
+    assert_eq!(dps.read_fifo(&mut samples).unwrap(), 2);
+    assert_eq!(samples[0], FifoSample::Pressure(100_000.0));
+    assert_eq!(samples[1], FifoSample::Temperature(20.0));
+    i2c.done();
+}
+
+#[test]
+fn test_read_fifo_timestamped() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    // 2 per second
+    expectations[2] = I2cTransaction::write(ADDR, vec![Register::PRS_CFG.addr(), 0x10]);
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PSR_B2.addr()],
+        vec![
+            0x00, 0x20, 0x01, // pressure
+            0x00, 0x10, 0x00, // temperature
+            0x00, 0x20, 0x01, // pressure
+            0x80, 0x00, 0x00, // empty
+        ],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut config = Config::new();
+    config.pres_rate(PressureRate::_2_SPS);
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let mut clock = || 2_000_000;
+    let mut samples = [Timestamped {
+        timestamp_us: 0,
+        value: FifoSample::Temperature(0.0),
+    }; 4];
+    assert_eq!(
+        dps.read_fifo_timestamped(&mut clock, &mut samples).unwrap(),
+        3
+    );
+    // Newest results end at the read, pressures are 500 ms apart, conversions take 3.6 ms
+    assert_eq!(
+        samples[..3]
+            .iter()
+            .map(|s| s.timestamp_us)
+            .collect::<Vec<_>>(),
+        [1_496_400, 1_996_400, 1_996_400]
+    );
+    assert_eq!(samples[1].value, FifoSample::Temperature(20.0));
+    i2c.done();
+}
+
+#[test]
+fn test_read_fifo_without_temperature_uses_result_register() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(
+            ADDR,
+            vec![Register::PSR_B2.addr()],
+            vec![0x00, 0x20, 0x01, 0x80, 0x00, 0x00],
+        ),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x10, 0x00]),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let mut samples = [FifoSample::Temperature(0.0); 2];
+    assert_eq!(dps.read_fifo(&mut samples).unwrap(), 1);
+    assert_eq!(samples[0], FifoSample::Pressure(100_000.0));
+    i2c.done();
+}
+
+#[test]
+fn test_flush_fifo() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.push(I2cTransaction::write(
+        ADDR,
+        vec![Register::RESET.addr(), 0x80],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    dps.flush_fifo().unwrap();
+    i2c.done();
+}
+
+#[test]
+fn test_interrupt_status() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::INT_STS.addr()],
+        vec![0x06],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    assert_eq!(
+        dps.interrupt_status().unwrap(),
+        InterruptStatus {
+            fifo_full: true,
+            temp_ready: true,
+            pres_ready: false
+        }
+    );
+    i2c.done();
+}
+
+#[test]
+fn test_try_read_interrupt_active_low() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::INT_STS.addr()], vec![0x01]),
+        I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::PSR_B2.addr()], vec![0x00, 0x00, 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
+    ]);
+    let pin_expectations = [
+        PinTransaction::get(PinState::High),
+        PinTransaction::get(PinState::Low),
+    ];
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut pin = PinMock::new(&pin_expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    assert!(matches!(
+        dps.try_read_interrupt(&mut pin),
+        Err(nb::Error::WouldBlock)
+    ));
+    let sample = dps.try_read_interrupt(&mut pin).unwrap();
+    assert!(sample.status.pres_ready);
+    assert_eq!(sample.pressure, Some(100_000.0));
+    assert_eq!(sample.temperature, None);
+    i2c.done();
+    pin.done();
+}
+
+#[test]
+fn test_try_read_interrupt_active_high() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations[6] = I2cTransaction::write(ADDR, vec![Register::CFG_REG.addr(), 0xA0]);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::INT_STS.addr()], vec![0x02]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x00, 0x00]),
+    ]);
+    let pin_expectations = [
+        PinTransaction::get(PinState::Low),
+        PinTransaction::get(PinState::High),
+    ];
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut pin = PinMock::new(&pin_expectations);
+    let mut config = Config::new();
+    config.int_hl(true).int_temp(true);
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    assert!(matches!(
+        dps.try_read_interrupt(&mut pin),
+        Err(nb::Error::WouldBlock)
+    ));
+    let sample = dps.try_read_interrupt(&mut pin).unwrap();
+    assert_eq!(sample.pressure, None);
+    assert_eq!(sample.temperature, Some(20.0));
+    i2c.done();
+    pin.done();
+}
+
+#[test]
+fn test_read_sample_single_burst() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PSR_B2.addr()],
+        vec![0x00, 0x04, 0x00, 0x00, 0x08, 0x00],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let sample = dps.read_sample().unwrap();
+    assert_eq!(sample.pressure_pa, 100_000.0);
+    assert_eq!(sample.temperature_c, 20.0);
+    i2c.done();
+}
+
+#[test]
+fn test_read_fixed_point() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x08, 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::PSR_B2.addr()], vec![0x00, 0x04, 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::TMP_B2.addr()], vec![0x00, 0x08, 0x00]),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    assert_eq!(dps.read_temp_fixed().unwrap(), 20 * TEMPERATURE_FIXED_SCALE);
+    assert_eq!(
+        dps.read_pressure_fixed().unwrap(),
+        100_000 * PRESSURE_FIXED_SCALE
+    );
+    i2c.done();
+}
+
+#[test]
+fn test_reset_and_reinit_keeps_calibration() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.push(I2cTransaction::write(
+        ADDR,
+        vec![Register::RESET.addr(), 0x89],
+    ));
+    // Same init sequence up to INIT_RDY, coefficients are not read again
+    let init = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend(init.iter().take(init.len() - 2).cloned());
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::PSR_B2.addr()],
+        vec![0x00, 0x04, 0x00, 0x00, 0x08, 0x00],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+    let mut dps = dps.reset_and_reinit(&mut TestDelay).unwrap();
+
+    let sample = dps.read_sample().unwrap();
+    assert_eq!(sample.pressure_pa, 100_000.0);
+    assert_eq!(sample.temperature_c, 20.0);
+    i2c.done();
+}
+
+#[test]
+fn test_reset_and_reinit_reports_stalled_stage() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.push(I2cTransaction::write(
+        ADDR,
+        vec![Register::RESET.addr(), 0x89],
+    ));
+    let init = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend(init.iter().take(13).cloned());
+    // INIT_RDY never set
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::MEAS_CFG.addr()],
+        vec![0x00],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let mut config = Config::new();
+    config.init_timeout_ms(20);
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    assert!(matches!(
+        dps.reset_and_reinit(&mut TestDelay),
+        Err(Error::InitTimeout(InitStage::WaitingInitComplete))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn test_reconfigure_writes_only_changed_registers() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.extend([
+        I2cTransaction::write_read(ADDR, vec![Register::MEAS_CFG.addr()], vec![0xC7]),
+        I2cTransaction::write(ADDR, vec![Register::MEAS_CFG.addr(), 0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::PRS_CFG.addr()], vec![0x00]),
+        I2cTransaction::write(ADDR, vec![Register::PRS_CFG.addr(), 0x44]),
+        I2cTransaction::write_read(ADDR, vec![Register::TEMP_CFG.addr()], vec![0x00]),
+        I2cTransaction::write_read(ADDR, vec![Register::CFG_REG.addr()], vec![0x00]),
+        I2cTransaction::write(ADDR, vec![Register::CFG_REG.addr(), 0x04]),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let mut flight = Config::new();
+    flight
+        .pres_rate(PressureRate::_16_SPS)
+        .pres_res(PressureResolution::_16_SAMPLES);
+    dps.reconfigure(&flight).unwrap();
+    i2c.done();
+}
+
+#[test]
+fn test_reconfigure_rejects_invalid_config() {
+    let expectations = calibrated_expectations(&TEST_COEFFS);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let mut fifo_int = Config::new();
+    fifo_int.int_fifo(true);
+    assert!(matches!(
+        dps.reconfigure(&fifo_int),
+        Err(Error::InvalidConfig(ConfigError {
+            fifo_interrupt_without_fifo: true,
+            ..
+        }))
+    ));
+    i2c.done();
+}
+
+#[test]
+fn test_reconfigure_rejects_busy_time() {
+    let mut expectations = calibrated_expectations(&TEST_COEFFS);
+    expectations.push(I2cTransaction::write_read(
+        ADDR,
+        vec![Register::MEAS_CFG.addr()],
+        vec![0xC7],
+    ));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new(i2c.clone(), ADDR, &config).unwrap();
+    let mut dps = dps.init_and_calibrate(&mut TestDelay).unwrap();
+
+    let mut too_fast = Config::new();
+    too_fast
+        .pres_rate(PressureRate::_128_SPS)
+        .pres_res(PressureResolution::_128_SAMPLES);
+    assert!(matches!(
+        dps.reconfigure(&too_fast),
+        Err(Error::BusyTimeExceeded)
+    ));
+    i2c.done();
+}
+
+#[test]
+fn test_new_rejects_invalid_config() {
+    let mut i2c = I2cMock::new(&[]);
+    let mut config = Config::new();
+    config.int_fifo(true);
+
+    match DPS3xx::new(i2c.clone(), ADDR, &config) {
+        Err(Error::InvalidConfig(error)) => assert!(error.fifo_interrupt_without_fifo),
+        _ => panic!("config should be rejected"),
+    }
+    i2c.done();
+}
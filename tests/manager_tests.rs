@@ -0,0 +1,165 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use uf_dps3xx::{
+    calc_total_wait_ms, Config, DPS3xx, DPS3xxManager, Error, MeasurementMode, Register,
+};
+
+pub mod common;
+
+use common::{Access, TEST_COEFFS};
+
+const ADDR_A: u8 = 0x77;
+const ADDR_B: u8 = 0x76;
+
+struct TestDelay {
+    total_ms: u32,
+}
+
+impl DelayNs for TestDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.total_ms += ns / 1_000_000;
+    }
+}
+
+fn i2c_transactions(addr: u8, accesses: Vec<Access>) -> Vec<I2cTransaction> {
+    accesses
+        .into_iter()
+        .map(|access| match access {
+            Access::Read(reg, response) => {
+                I2cTransaction::write_read(addr, vec![reg.addr()], response)
+            }
+            Access::Write(reg_addr, value) => I2cTransaction::write(addr, vec![reg_addr, value]),
+        })
+        .collect()
+}
+
+fn start_init_expectations(addr: u8) -> Vec<I2cTransaction> {
+    i2c_transactions(addr, common::start_init(0x00))
+}
+
+fn first_poll_expectations(addr: u8) -> Vec<I2cTransaction> {
+    i2c_transactions(addr, common::first_poll())
+}
+
+fn second_poll_expectations(addr: u8) -> Vec<I2cTransaction> {
+    i2c_transactions(addr, common::second_poll(&TEST_COEFFS))
+}
+
+#[test]
+fn test_init_all_interleaves_sensors() {
+    let mut expectations = start_init_expectations(ADDR_A);
+    expectations.extend(start_init_expectations(ADDR_B));
+    expectations.extend(first_poll_expectations(ADDR_A));
+    expectations.extend(first_poll_expectations(ADDR_B));
+    expectations.extend(second_poll_expectations(ADDR_A));
+    expectations.extend(second_poll_expectations(ADDR_B));
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let mut manager = DPS3xxManager::new([
+        DPS3xx::new(i2c.clone(), ADDR_A, &config).unwrap(),
+        DPS3xx::new(i2c.clone(), ADDR_B, &config).unwrap(),
+    ]);
+    let mut delay = TestDelay { total_ms: 0 };
+
+    let [a, b] = manager.init_all(&mut delay);
+    assert!(a.is_ok(), "sensor A should be calibrated");
+    assert!(b.is_ok(), "sensor B should be calibrated");
+    // One shared wait instead of one per sensor
+    assert_eq!(delay.total_ms, calc_total_wait_ms(0, 0));
+    i2c.done();
+}
+
+#[test]
+fn test_failed_sensor_does_not_abort_others() {
+    let mut expectations = start_init_expectations(ADDR_A);
+    expectations.push(I2cTransaction::write_read(
+        ADDR_B,
+        vec![Register::PROD_ID.addr()],
+        vec![0x20],
+    ));
+    expectations.extend(first_poll_expectations(ADDR_A));
+    expectations.extend(second_poll_expectations(ADDR_A));
+    expectations.extend([
+        I2cTransaction::write_read(ADDR_A, vec![Register::MEAS_CFG.addr()], vec![0xC0]),
+        I2cTransaction::write(ADDR_A, vec![Register::MEAS_CFG.addr(), 0xC7]),
+        I2cTransaction::write_read(ADDR_A, vec![Register::MEAS_CFG.addr()], vec![0xF7]),
+        I2cTransaction::write_read(
+            ADDR_A,
+            vec![Register::PSR_B2.addr()],
+            vec![0x00, 0x04, 0x00, 0x00, 0x08, 0x00],
+        ),
+    ]);
+
+    let mut i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let mut manager = DPS3xxManager::new([
+        DPS3xx::new(i2c.clone(), ADDR_A, &config).unwrap(),
+        DPS3xx::new(i2c.clone(), ADDR_B, &config).unwrap(),
+    ]);
+    let mut delay = TestDelay { total_ms: 0 };
+
+    let [a, b] = manager.init_all(&mut delay);
+    assert!(a.is_ok(), "sensor A should be calibrated");
+    assert!(matches!(b, Err(Error::InvalidProductId)));
+    assert!(manager.sensor_mut(0).is_some());
+    assert!(manager.sensor_mut(1).is_none());
+
+    let [a, b] = manager.start_all(MeasurementMode::BackgroundPressureAndTemperature);
+    assert!(a.is_ok(), "measurement should start on sensor A");
+    assert!(matches!(b, Err(Error::NotInitialized)));
+
+    let mut now_us = 10_000;
+    let mut clock = || {
+        now_us += 1_000;
+        now_us
+    };
+    let [a, b] = manager.read_all(&mut clock);
+    // Backdated by the 3.6 ms pressure conversion at the default oversampling
+    assert_eq!(a.timestamp_us, 11_000 - 3_600);
+    let measurement = a.measurement.unwrap();
+    assert_eq!(measurement.pressure_pa, 100_000.0);
+    assert_eq!(measurement.temperature_c, 20.0);
+    assert_eq!(b.timestamp_us, 12_000);
+    assert!(matches!(b.measurement, Err(Error::NotInitialized)));
+    i2c.done();
+}
+
+#[test]
+fn test_init_all_times_out_per_sensor() {
+    let mut expectations = start_init_expectations(ADDR_A);
+    expectations.extend(start_init_expectations(ADDR_B));
+    expectations.extend(first_poll_expectations(ADDR_A));
+    // Sensor B never completes init
+    expectations.push(I2cTransaction::write_read(
+        ADDR_B,
+        vec![Register::MEAS_CFG.addr()],
+        vec![0x00],
+    ));
+    expectations.extend(second_poll_expectations(ADDR_A));
+    expectations.push(I2cTransaction::write_read(
+        ADDR_B,
+        vec![Register::MEAS_CFG.addr()],
+        vec![0x00],
+    ));
+
+    let i2c = I2cMock::new(&expectations);
+    let config = Config::new();
+    let mut short_timeout = Config::new();
+    short_timeout.init_timeout_ms(15);
+    let mut manager = DPS3xxManager::new([
+        DPS3xx::new(i2c.clone(), ADDR_A, &config).unwrap(),
+        DPS3xx::new(i2c.clone(), ADDR_B, &short_timeout).unwrap(),
+    ]);
+    let mut delay = TestDelay { total_ms: 0 };
+
+    let [a, b] = manager.init_all(&mut delay);
+    assert!(a.is_ok(), "sensor A should be calibrated");
+    assert!(matches!(b, Err(Error::InitTimeout(_))));
+
+    // The timed out sensor's bus is handed back too
+    let [a, b] = manager.release();
+    assert!(a.is_some(), "sensor A should be released");
+    let mut i2c = b.unwrap().release();
+    i2c.done();
+}
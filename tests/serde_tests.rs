@@ -0,0 +1,100 @@
+#![cfg(feature = "serde")]
+
+use uf_dps3xx::{
+    Config, Measurement, MeasurementMode, PressureRate, PressureResolution, Status, TemperatureRate,
+};
+
+#[test]
+fn test_enums_serialize_as_names() {
+    assert_eq!(
+        serde_json::to_string(&PressureRate::_16_SPS).unwrap(),
+        "\"16_SPS\""
+    );
+    assert_eq!(
+        serde_json::to_string(&PressureResolution::_64_SAMPLES).unwrap(),
+        "\"64_SAMPLES\""
+    );
+}
+
+#[test]
+fn test_enums_deserialize_from_name_or_register_value() {
+    let rate: TemperatureRate = serde_json::from_str("\"32_SPS\"").unwrap();
+    assert_eq!(rate, TemperatureRate::_32_SPS);
+    let rate: TemperatureRate = serde_json::from_str("\"_32_SPS\"").unwrap();
+    assert_eq!(rate, TemperatureRate::_32_SPS);
+    let rate: TemperatureRate = serde_json::from_str("5").unwrap();
+    assert_eq!(rate, TemperatureRate::_32_SPS);
+
+    assert!(serde_json::from_str::<TemperatureRate>("8").is_err());
+    assert!(serde_json::from_str::<TemperatureRate>("\"3_SPS\"").is_err());
+}
+
+#[test]
+fn test_config_round_trip() {
+    let config = Config::indoor_navigation();
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(json.contains("\"pres_res\":\"16_SAMPLES\""), "{json}");
+
+    let decoded: Config = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+}
+
+#[test]
+fn test_config_missing_fields_use_defaults() {
+    let config: Config = serde_json::from_str(r#"{"pres_rate": 4}"#).unwrap();
+    let mut expected = Config::new();
+    expected.pres_rate(PressureRate::_16_SPS);
+    assert_eq!(
+        serde_json::to_value(config).unwrap(),
+        serde_json::to_value(expected).unwrap()
+    );
+}
+
+#[test]
+fn test_config_rejects_invalid_combination() {
+    let err = serde_json::from_str::<Config>(r#"{"int_fifo": true, "fifo_enable": false}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("FIFO interrupt"), "{err}");
+}
+
+#[test]
+fn test_measurement_and_status_round_trip() {
+    let measurement = Measurement {
+        pressure_pa: 100_000.0,
+        temperature_c: 20.5,
+    };
+    let json = serde_json::to_string(&measurement).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Measurement>(&json).unwrap(),
+        measurement
+    );
+
+    let status = Status {
+        coef_ready: true,
+        init_complete: true,
+        temp_ready: false,
+        pres_ready: true,
+    };
+    let json = serde_json::to_string(&status).unwrap();
+    assert_eq!(serde_json::from_str::<Status>(&json).unwrap(), status);
+
+    let mode: MeasurementMode = serde_json::from_str("\"BackgroundPressure\"").unwrap();
+    assert_eq!(mode, MeasurementMode::BackgroundPressure);
+}
+
+#[test]
+fn test_config_round_trip_non_self_describing() {
+    let config = Config::indoor_navigation();
+    let mut buf = [0; 64];
+    let bytes = postcard::to_slice(&config, &mut buf).unwrap();
+
+    let decoded: Config = postcard::from_bytes(bytes).unwrap();
+    assert_eq!(
+        serde_json::to_value(decoded).unwrap(),
+        serde_json::to_value(config).unwrap()
+    );
+
+    let rate: PressureRate = postcard::from_bytes(&[4]).unwrap();
+    assert_eq!(rate, PressureRate::_16_SPS);
+}
@@ -0,0 +1,89 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+use uf_dps3xx::{Config, DPS3xx, Register};
+
+pub mod common;
+
+use common::{Access, TEST_COEFFS};
+
+struct TestDelay;
+
+impl DelayNs for TestDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+fn read(reg: Register, response: Vec<u8>) -> [SpiTransaction<u8>; 4] {
+    [
+        SpiTransaction::transaction_start(),
+        SpiTransaction::write_vec(vec![reg.addr() | 0x80]),
+        SpiTransaction::read_vec(response),
+        SpiTransaction::transaction_end(),
+    ]
+}
+
+fn write(addr: u8, value: u8) -> [SpiTransaction<u8>; 3] {
+    [
+        SpiTransaction::transaction_start(),
+        SpiTransaction::write_vec(vec![addr, value]),
+        SpiTransaction::transaction_end(),
+    ]
+}
+
+fn init_expectations(cfg_reg: u8) -> Vec<SpiTransaction<u8>> {
+    let mut expectations = Vec::new();
+    for access in common::init_and_calibrate(cfg_reg, &TEST_COEFFS) {
+        match access {
+            Access::Read(reg, response) => expectations.extend(read(reg, response)),
+            Access::Write(addr, value) => expectations.extend(write(addr, value)),
+        }
+    }
+    expectations
+}
+
+#[test]
+fn test_spi_four_wire_init_and_calibrate() {
+    let expectations = init_expectations(0x00);
+
+    let mut spi = SpiMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new_spi(spi.clone(), &config).unwrap();
+    let mut delay = TestDelay;
+
+    let _dps = dps.init_and_calibrate(&mut delay).unwrap();
+    spi.done();
+}
+
+#[test]
+fn test_spi_three_wire_selected_before_first_read() {
+    let mut expectations = Vec::new();
+    expectations.extend(write(Register::CFG_REG.addr(), 0x01));
+    expectations.extend(init_expectations(0x01));
+
+    let mut spi = SpiMock::new(&expectations);
+    let mut config = Config::new();
+    config.spi_mode(true);
+    let dps = DPS3xx::new_spi(spi.clone(), &config).unwrap();
+    let mut delay = TestDelay;
+
+    let _dps = dps.init_and_calibrate(&mut delay).unwrap();
+    spi.done();
+}
+
+#[test]
+fn test_spi_read_pressure_calibrated() {
+    let mut expectations = init_expectations(0x00);
+    expectations.extend(read(Register::PRS_CFG, vec![0x00]));
+    expectations.extend(read(Register::PSR_B2, vec![0x00, 0x04, 0x00]));
+    expectations.extend(read(Register::TEMP_CFG, vec![0x00]));
+    expectations.extend(read(Register::TMP_B2, vec![0x00, 0x04, 0x00]));
+
+    let spi = SpiMock::new(&expectations);
+    let config = Config::new();
+    let dps = DPS3xx::new_spi(spi, &config).unwrap();
+    let mut delay = TestDelay;
+    let mut dps = dps.init_and_calibrate(&mut delay).unwrap();
+
+    assert_eq!(dps.read_pressure_calibrated().unwrap(), 100_000.0);
+    let mut released_spi = dps.release();
+    released_spi.done();
+}